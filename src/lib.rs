@@ -1,3 +1,7 @@
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+extern crate alloc;
+
 /// 🔎 Social Mentions and Hashtags Extractor
 ///
 /// A lightweight utility for extracting unique `@mentions` and `#hashtags` from social media-style text.
@@ -7,25 +11,417 @@
 /// # Overview
 ///
 /// - Extracts both mentions (e.g., `@MrBeast`) and hashtags (e.g., `#fyp`) using regular expressions
-/// - Ensures uniqueness with deduplication via `HashSet`
+/// - Ensures uniqueness with deduplication via `HashSet`, keeping first-seen order
 /// - Case-insensitive matching but preserves original casing
 /// - Supports optional parsing (mentions-only, hashtags-only, or both)
+/// - Works in `no_std` + `alloc` environments when the default `std` feature is disabled
+///
+/// # `no_std`
+///
+/// Without `std` there is no `HashMap` / `HashSet`, so every public signature that mentions
+/// them uses `BTreeMap` / `BTreeSet` instead (e.g. [`count_hashtags`](mentions_hashtags::count_hashtags)
+/// returns a `BTreeMap`). Disabling `std` therefore changes types, not just availability:
+/// code written against the `std` signatures may need adjusting, and map iteration becomes
+/// sorted by key.
 pub mod mentions_hashtags {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        borrow::Cow,
+        boxed::Box,
+        collections::{BTreeMap, BTreeMap as HashMap, BTreeSet as HashSet},
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use core::{fmt, ops::Range};
     use regex::Regex;
-    use std::collections::HashSet;
-    use std::error::Error;
+    #[cfg(feature = "std")]
+    use std::{
+        borrow::Cow,
+        collections::{BTreeMap, HashMap, HashSet},
+    };
+
+    /// Error returned by the fallible functions in this module.
+    ///
+    /// Callers can `match` on the variant instead of downcasting a boxed error.
+    #[derive(Debug, Clone, PartialEq)]
+    #[non_exhaustive]
+    pub enum ParseError {
+        /// A pattern built from caller input (e.g. a custom charset) failed to compile.
+        Regex(regex::Error),
+    }
+
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ParseError::Regex(err) => write!(f, "invalid pattern: {err}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for ParseError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                ParseError::Regex(err) => Some(err),
+            }
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    impl core::error::Error for ParseError {}
+
+    impl From<regex::Error> for ParseError {
+        fn from(err: regex::Error) -> Self {
+            ParseError::Regex(err)
+        }
+    }
+
+    /// Expands to a `&'static Regex` for a constant pattern, compiled once on first use.
+    macro_rules! cached_regex {
+        ($pattern:ident) => {
+            cached_regex!(Regex, $pattern)
+        };
+        ($regex:ty, $pattern:ident) => {{
+            #[cfg(feature = "std")]
+            static REGEX: std::sync::OnceLock<$regex> = std::sync::OnceLock::new();
+            #[cfg(not(feature = "std"))]
+            static REGEX: once_cell::race::OnceBox<$regex> = once_cell::race::OnceBox::new();
+            REGEX.get_or_init(|| {
+                let regex = <$regex>::new($pattern)
+                    .expect(concat!(stringify!($pattern), " is a valid regex"));
+                #[cfg(not(feature = "std"))]
+                let regex = Box::new(regex);
+                regex
+            })
+        }};
+    }
+
+    /// Punctuation allowed inside a token body by default, on top of letters, digits and marks.
+    ///
+    /// Pass a different set to [`parse_mentions_with_charset`] / [`parse_hashtags_with_charset`]
+    /// to restrict or extend it (e.g. `"_"` for Twitter-style handles).
+    pub const DEFAULT_TOKEN_CHARS: &str = "_-.";
+
+    /// The longest token body, in characters, that a validating [`Extractor`] accepts.
+    ///
+    /// Guards against pathological input such as a 10,000-character "hashtag" with no spaces.
+    /// Applied by [`ExtractorBuilder::validating`] and [`sanitize_hashtags`]; set it on any
+    /// other extractor with [`ExtractorBuilder::max_len`]. Plain builders and the free
+    /// functions such as [`hashtags`] have no length limit.
+    pub const MAX_TOKEN_LEN: usize = 140;
+
+    /// Pattern for a single `@mention`, as used by [`parse_mentions`].
+    ///
+    /// Token bodies accept Unicode letters (`\p{L}`), numbers (`\p{N}`) and combining marks
+    /// (`\p{M}`) from any script, plus [`DEFAULT_TOKEN_CHARS`]. The first character after the
+    /// sigil must be a letter, digit or `_`, so `@-foo` and `@.foo` are not mentions, while
+    /// `-` and `.` are fine anywhere after it (`@Khaby.Lame`, `@end.`).
+    ///
+    /// Any other character ends the token. That includes the replacement character U+FFFD
+    /// left behind by a lossy UTF-8 decode (it is a symbol, not a letter), so `@jo\u{FFFD}e`
+    /// yields `@jo`; it is never stripped or kept inside a token.
+    ///
+    /// The full-width at sign `＠` (U+FF20), typed by some mobile keyboards, is accepted as a
+    /// sigil too. Tokens are always returned as written, so `＠MrBeast` keeps its full-width
+    /// sigil and is distinct from `@MrBeast`.
+    ///
+    /// The pattern starts with an inline `(?i)` flag, so wrap it in a group (`(?:...)`) when
+    /// embedding it in a larger regex. It does not include the word-boundary check that skips
+    /// e-mail addresses; [`parse_mentions`] applies that separately.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{HASHTAG_PATTERN, MENTION_PATTERN};
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(&format!(r"(?:{MENTION_PATTERN})|(?:{HASHTAG_PATTERN})|https?://\S+")).unwrap();
+    /// let found: Vec<&str> = re.find_iter("@a #b https://x.y").map(|m| m.as_str()).collect();
+    /// assert_eq!(found, vec!["@a", "#b", "https://x.y"]);
+    /// ```
+    pub const MENTION_PATTERN: &str = r"(?i)[@＠][\p{L}\p{N}_][\p{L}\p{N}\p{M}_\-.]*";
+
+    /// Pattern for a single `#hashtag`, as used by [`parse_hashtags`].
+    ///
+    /// Same character class and `(?i)` prefix as [`MENTION_PATTERN`]; the full-width hash `＃`
+    /// (U+FF03) is accepted as a sigil too.
+    ///
+    /// `#` is never part of a token body, so hashtags written without spaces split at each
+    /// sigil: `#fyp#viral#foryou` yields `#fyp`, `#viral` and `#foryou`. (A mention glued to a
+    /// word is skipped instead, so `#fyp@user` yields no mention.)
+    pub const HASHTAG_PATTERN: &str = r"(?i)[#＃][\p{L}\p{N}_][\p{L}\p{N}\p{M}_\-.]*";
+
+    /// Pattern for a `local@domain.tld` e-mail address; the domain needs at least one dot.
+    const EMAIL_PATTERN: &str = r"[\p{L}\p{N}._%+\-]+@[\p{L}\p{N}\-]+(?:\.[\p{L}\p{N}\-]+)+";
+
+    /// Pattern for one non-ASCII emoji character, including the joiners, variation selectors,
+    /// keycap marks and skin-tone modifiers that end emoji sequences.
+    const EMOJI_PATTERN: &str = r"^[\p{Extended_Pictographic}\p{Emoji_Component}&&[^\x00-\x7F]]$";
+
+    /// Pattern for a URL: a scheme or `www.` prefix, then everything up to the next whitespace.
+    const URL_PATTERN: &str = r"(?i)\b(?:[a-z][a-z0-9+.\-]*://|www\.)\S+";
+
+    /// Full-width sigils (U+FF20, U+FF03), matched alongside `@` and `#`.
+    const FULLWIDTH_AT: char = '＠';
+    const FULLWIDTH_HASH: char = '＃';
+
+    /// Pattern for a single `$CASHTAG`; the ticker must start with a letter so `$100` is skipped.
+    const CASHTAG_PATTERN: &str = r"\$[a-zA-Z][a-zA-Z0-9.\-]*";
+
+    /// A letter, number or combining mark in any script: a char that would continue a token.
+    const WORD_CHAR_PATTERN: &str = r"^[\p{L}\p{N}\p{M}]$";
+
+    /// Candidate `@handle` patterns per platform; [`Platform::is_valid_handle`] does the rest.
+    const TWITTER_MENTION_PATTERN: &str = r"[@＠][A-Za-z0-9_]+";
+    const INSTAGRAM_MENTION_PATTERN: &str = r"[@＠][A-Za-z0-9_.]+";
+    const TIKTOK_MENTION_PATTERN: &str = r"[@＠][A-Za-z0-9_.]+";
+
+    /// Pattern for a mention with an optional `@instance.domain` suffix, as on Mastodon.
+    const FEDIVERSE_MENTION_PATTERN: &str =
+        r"(?i)[@＠][\p{L}\p{N}_][\p{L}\p{N}\p{M}_\-.]*(?:@[\p{L}\p{N}\-]+(?:\.[\p{L}\p{N}\-]+)+)?";
+
+    /// Pattern matching either kind of token; the first character tells them apart.
+    const TOKEN_PATTERN: &str = r"(?i)[@#＠＃][\p{L}\p{N}_][\p{L}\p{N}\p{M}_\-.]*";
 
     /// Represents the result of parsing social text for mentions and hashtags.
     ///
     /// # Fields
     /// - `mentions`: A list of unique `@username` strings
     /// - `hashtags`: A list of unique `#topic` strings
+    ///
+    /// With the `serde` feature enabled this serializes as `{"mentions": [...], "hashtags": [...]}`.
     #[derive(Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct MentionsHashtags {
         pub mentions: Vec<String>,
         pub hashtags: Vec<String>,
     }
 
+    impl MentionsHashtags {
+        /// Whether no mention and no hashtag was found.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::MentionsHashtags;
+        ///
+        /// assert!(MentionsHashtags::from("plain text").is_empty());
+        /// assert!(!MentionsHashtags::from("#fyp").is_empty());
+        /// ```
+        pub fn is_empty(&self) -> bool {
+            self.mentions.is_empty() && self.hashtags.is_empty()
+        }
+
+        /// The number of mentions plus the number of hashtags.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::MentionsHashtags;
+        ///
+        /// assert_eq!(MentionsHashtags::from("@a @b #fyp #fyp").len(), 3);
+        /// ```
+        pub fn len(&self) -> usize {
+            self.mentions.len() + self.hashtags.len()
+        }
+
+        /// Adds the tokens of `other` that are not already present, keeping order.
+        ///
+        /// Tokens already in `self` stay first, followed by the new ones from `other` in their
+        /// order. Comparison is exact, like [`parse_mentions_hashtags`].
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::MentionsHashtags;
+        ///
+        /// let mut title = MentionsHashtags::from("@a #x");
+        /// title.merge(MentionsHashtags::from("#x #y @b"));
+        /// assert_eq!(title.mentions, vec!["@a", "@b"]);
+        /// assert_eq!(title.hashtags, vec!["#x", "#y"]);
+        /// ```
+        pub fn merge(&mut self, other: MentionsHashtags) {
+            merge_tokens(&mut self.mentions, other.mentions, str::to_string);
+            merge_tokens(&mut self.hashtags, other.hashtags, str::to_string);
+        }
+
+        /// Like [`MentionsHashtags::merge`], but tokens differing only by case count as
+        /// duplicates, as in [`parse_mentions_dedup_ci`]; the casing already in `self` wins.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::MentionsHashtags;
+        ///
+        /// let mut merged = MentionsHashtags::from("#Music");
+        /// merged.merge_dedup_ci(MentionsHashtags::from("#music #fyp"));
+        /// assert_eq!(merged.hashtags, vec!["#Music", "#fyp"]);
+        /// ```
+        pub fn merge_dedup_ci(&mut self, other: MentionsHashtags) {
+            merge_tokens(&mut self.mentions, other.mentions, canonical_key);
+            merge_tokens(&mut self.hashtags, other.hashtags, canonical_key);
+        }
+    }
+
+    impl From<&str> for MentionsHashtags {
+        /// Extracts both mentions and hashtags with default options, like
+        /// `parse_mentions_hashtags(text, true, true)` without the `Result`.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::MentionsHashtags;
+        ///
+        /// let mh = MentionsHashtags::from("@MrBeast new video #fyp");
+        /// assert_eq!(mh.mentions, vec!["@MrBeast"]);
+        ///
+        /// let mh: MentionsHashtags = "#a #b".into();
+        /// assert_eq!(mh.hashtags, vec!["#a", "#b"]);
+        /// ```
+        fn from(text: &str) -> Self {
+            extract(text, true, true)
+        }
+    }
+
+    /// Unique mentions and hashtags together with their occurrence counts, from one pass.
+    ///
+    /// # Fields
+    /// - `mentions` / `hashtags`: Unique tokens in order of first appearance, as in
+    ///   [`MentionsHashtags`]
+    /// - `mention_counts` / `hashtag_counts`: How often each token occurs, as in
+    ///   [`count_mentions`] / [`count_hashtags`]
+    #[derive(Debug, Default, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ParseStats {
+        pub mentions: Vec<String>,
+        pub mention_counts: HashMap<String, usize>,
+        pub hashtags: Vec<String>,
+        pub hashtag_counts: HashMap<String, usize>,
+    }
+
+    /// The result of [`parse_mentions_outcome`] / [`parse_hashtags_outcome`], telling blank
+    /// input apart from input without tokens.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ParseOutcome {
+        /// The input was empty or only whitespace.
+        Empty,
+        /// The input had text; the `Vec` holds its unique tokens and may itself be empty.
+        Matches(Vec<String>),
+    }
+
+    impl ParseOutcome {
+        /// Returns the tokens, treating [`ParseOutcome::Empty`] as no tokens.
+        pub fn into_vec(self) -> Vec<String> {
+            match self {
+                ParseOutcome::Empty => Vec::new(),
+                ParseOutcome::Matches(tokens) => tokens,
+            }
+        }
+    }
+
+    /// A mention in both forms: as written and without the sigil.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Mention {
+        /// The mention as written, including the sigil (e.g. `@MrBeast`).
+        pub raw: String,
+        /// The bare handle (e.g. `MrBeast`).
+        pub handle: String,
+    }
+
+    /// A hashtag in both forms: as written and without the sigil.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Hashtag {
+        /// The hashtag as written, including the sigil (e.g. `#fyp`).
+        pub raw: String,
+        /// The bare tag (e.g. `fyp`).
+        pub tag: String,
+    }
+
+    /// A token found in the text, tagged with its kind.
+    ///
+    /// With the `serde` feature this serializes as `{"kind": "mention", "text": "@MrBeast"}`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(tag = "kind", content = "text", rename_all = "lowercase")
+    )]
+    pub enum Token {
+        /// An `@mention`, including the sigil (e.g. `@MrBeast`).
+        Mention(String),
+        /// A `#hashtag`, including the sigil (e.g. `#fyp`).
+        Hashtag(String),
+    }
+
+    impl Token {
+        /// Returns the token text, including its sigil.
+        pub fn as_str(&self) -> &str {
+            match self {
+                Token::Mention(text) | Token::Hashtag(text) => text,
+            }
+        }
+
+        /// Returns the token's kind.
+        pub fn kind(&self) -> TokenKind {
+            match self {
+                Token::Mention(_) => TokenKind::Mention,
+                Token::Hashtag(_) => TokenKind::Hashtag,
+            }
+        }
+    }
+
+    /// The kind of a token, as reported by [`visit_tokens`].
+    ///
+    /// With the `serde` feature this serializes as `"mention"` or `"hashtag"`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(rename_all = "lowercase")
+    )]
+    pub enum TokenKind {
+        /// An `@mention`.
+        Mention,
+        /// A `#hashtag`.
+        Hashtag,
+    }
+
+    /// A token with its kind and byte span, as returned by [`parse_token_spans`].
+    ///
+    /// With the `serde` feature this serializes as
+    /// `{"kind": "mention", "text": "@x", "start": 0, "end": 2}`, ready for a frontend.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct TokenSpan {
+        /// Whether the token is a mention or a hashtag.
+        pub kind: TokenKind,
+        /// The token, including its sigil.
+        pub text: String,
+        /// Byte offset of the token's first byte.
+        pub start: usize,
+        /// Byte offset just past the token's last byte.
+        pub end: usize,
+    }
+
+    /// Where a token occurs in the text, for pointing at it in diagnostics.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct TokenLocation {
+        /// The token, including its sigil.
+        pub text: String,
+        /// 1-based line number.
+        pub line: usize,
+        /// 1-based column, counted in characters (Unicode scalar values) from the line start.
+        pub column: usize,
+        /// Byte offset of the token's first byte.
+        pub byte_start: usize,
+        /// Byte offset just past the token's last byte.
+        pub byte_end: usize,
+    }
+
+    /// A single matched token with its byte offsets: `(token, start, end)`.
+    ///
+    /// `start` and `end` index into the original input, so `&description[start..end] == token`.
+    pub type Span = (String, usize, usize);
+
     /// Parses the given description and extracts mentions and/or hashtags.
     ///
     /// # Arguments
@@ -39,11 +435,13 @@ pub mod mentions_hashtags {
     ///
     /// # Behavior
     /// - If both `mentions` and `hashtags` are false, returns empty vectors.
-    /// - Extracted values are **unique** and maintain original case.
+    /// - Extracted values are **unique**, maintain original case, and keep the order in which
+    ///   they first appear in `description`.
+    /// - The text is scanned once, regardless of how many kinds are requested.
     ///
     /// # Examples
     /// ```
-    /// use mentions_hashtags_rs::mentions_hashtags::parse_mentions_hashtags;
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_hashtags;
     ///
     /// let text = "@MrBeast check out the #fyp and #Challenge2025!";
     /// let result = parse_mentions_hashtags(text, true, true).unwrap();
@@ -55,20 +453,35 @@ pub mod mentions_hashtags {
         description: &str,
         mentions: bool,
         hashtags: bool,
-    ) -> Result<MentionsHashtags, Box<dyn Error>> {
+    ) -> Result<MentionsHashtags, ParseError> {
+        Ok(extract(description, mentions, hashtags))
+    }
+
+    /// Infallible core of [`parse_mentions_hashtags`].
+    fn extract(description: &str, mentions: bool, hashtags: bool) -> MentionsHashtags {
         let mut mentions_hashtags = MentionsHashtags::default();
 
         if !mentions && !hashtags {
-            return Ok(mentions_hashtags);
-        }
-        if mentions {
-            mentions_hashtags.mentions = parse_mentions(description)?;
+            return mentions_hashtags;
         }
-        if hashtags {
-            mentions_hashtags.hashtags = parse_hashtags(description)?;
+
+        // One pass over the text; the sigil tells us which list a match belongs to.
+        let mut found_mentions = Vec::new();
+        let mut found_hashtags = Vec::new();
+        for m in find_tokens(token_regex(), description) {
+            let token = m.as_str();
+            if is_mention_token(token) {
+                if mentions {
+                    found_mentions.push(token);
+                }
+            } else if hashtags {
+                found_hashtags.push(token);
+            }
         }
+        mentions_hashtags.mentions = dedup_in_order(found_mentions.into_iter());
+        mentions_hashtags.hashtags = dedup_in_order(found_hashtags.into_iter());
 
-        Ok(mentions_hashtags)
+        mentions_hashtags
     }
 
     /// Extracts unique `@mentions` from the input text.
@@ -80,24 +493,53 @@ pub mod mentions_hashtags {
     /// A `Result` containing a `Vec<String>` of unique mentions.
     ///
     /// # Behavior
-    /// - Matches alphanumeric usernames including `_`, `-`, and `.`
-    /// - Preserves original casing (e.g., `@PewDiePie`, `@pewdiepie` both included if present)
+    /// - Thin wrapper around [`mentions`], kept for backward compatibility; never returns `Err`
     ///
     /// # Examples
     /// ```
-    /// use mentions_hashtags_rs::mentions_hashtags::parse_mentions;
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions;
     ///
     /// let result = parse_mentions("@charlidamelio @Khaby.Lame").unwrap();
     /// assert!(result.contains(&"@charlidamelio".to_string()));
     /// assert!(result.contains(&"@Khaby.Lame".to_string()));
     /// ```
-    pub fn parse_mentions(description: &str) -> Result<Vec<String>, Box<dyn Error>> {
-        let matches = Regex::new(r"(?i)@[a-zA-Z0-9_\-.]+")?;
-        let unique_mentions: HashSet<String> = matches
-            .find_iter(description)
-            .map(|m| m.as_str().to_string())
-            .collect();
-        Ok(unique_mentions.into_iter().collect())
+    pub fn parse_mentions(description: &str) -> Result<Vec<String>, ParseError> {
+        Ok(mentions(description))
+    }
+
+    /// Extracts unique `@mentions` from raw bytes, skipping UTF-8 validation.
+    ///
+    /// # Arguments
+    /// - `description`: The input bytes (e.g., a file read with `std::fs::read`)
+    ///
+    /// # Returns
+    /// A `Result` containing the unique mentions as byte strings, in order of first appearance.
+    ///
+    /// # Behavior
+    /// - Same rules as [`parse_mentions`], using a [`regex::bytes::Regex`]; never returns `Err`
+    /// - Meant for ASCII-compatible input. UTF-8 token bodies are matched like in
+    ///   [`parse_mentions`], but bytes that aren't valid UTF-8 end a token, so `@jos\xE9`
+    ///   (Latin-1) yields `@jos`
+    /// - Not a speed-up: on valid UTF-8, `std::str::from_utf8` plus [`parse_mentions`] is about
+    ///   as fast or faster (see the `bytes_input` benchmark), since validation is cheap. Use
+    ///   this for bytes that may not be valid UTF-8, without a lossy conversion
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_bytes;
+    ///
+    /// let found = parse_mentions_bytes(b"@a me@mail.com @b \xFF@a").unwrap();
+    /// assert_eq!(found, vec![b"@a".to_vec(), b"@b".to_vec()]);
+    /// ```
+    pub fn parse_mentions_bytes(description: &[u8]) -> Result<Vec<Vec<u8>>, ParseError> {
+        let mut seen = HashSet::new();
+        let mut unique = Vec::new();
+        for m in cached_regex!(regex::bytes::Regex, MENTION_PATTERN).find_iter(description) {
+            if is_mention_start_bytes(description, m.start()) && seen.insert(m.as_bytes()) {
+                unique.push(m.as_bytes().to_vec());
+            }
+        }
+        Ok(unique)
     }
 
     /// Extracts unique `#hashtags` from the input text.
@@ -109,124 +551,6512 @@ pub mod mentions_hashtags {
     /// A `Result` containing a `Vec<String>` of unique hashtags.
     ///
     /// # Behavior
-    /// - Matches alphanumeric hashtags including `_`, `-`, and `.`
-    /// - Preserves original casing (e.g., `#Music` and `#music` both included if present)
+    /// - Thin wrapper around [`hashtags`], kept for backward compatibility; never returns `Err`
     ///
     /// # Examples
     /// ```
-    /// use mentions_hashtags_rs::mentions_hashtags::parse_hashtags;
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags;
     ///
     /// let result = parse_hashtags("#fyp #CapCut #go_crazy.").unwrap();
     /// assert!(result.contains(&"#CapCut".to_string()));
     /// assert!(result.contains(&"#go_crazy.".to_string()));
     /// ```
-    pub fn parse_hashtags(description: &str) -> Result<Vec<String>, Box<dyn Error>> {
-        let matches = Regex::new(r"(?i)#[a-zA-Z0-9_\-.]+")?;
-        let unique_hashtags: HashSet<String> = matches
-            .find_iter(description)
-            .map(|x| x.as_str().to_string())
-            .collect();
-        Ok(unique_hashtags.into_iter().collect())
+    pub fn parse_hashtags(description: &str) -> Result<Vec<String>, ParseError> {
+        Ok(hashtags(description))
     }
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::mentions_hashtags::*;
-    use std::collections::HashSet;
 
-    // === Mentions Tests ===
-    #[test]
-    fn test_youtube_mentions() {
-        let result = parse_mentions("@MrBeast @EmmaChamberlain @PewDiePie").unwrap();
-        let expected: HashSet<_> = ["@MrBeast", "@EmmaChamberlain", "@PewDiePie"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    /// Extracts unique `@mentions` from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., TikTok or YouTube description)
+    ///
+    /// # Returns
+    /// A `Vec<String>` of unique mentions.
+    ///
+    /// # Behavior
+    /// - Matches usernames made of letters, digits and marks from any script, plus `_`, `-`, `.`
+    /// - Ignores an `@` that directly follows a letter, digit or `_`, so email addresses like
+    ///   `john@example.com` are not mistaken for mentions
+    /// - Preserves original casing (e.g., `@PewDiePie`, `@pewdiepie` both included if present)
+    /// - Returns mentions in order of first appearance; later duplicates are dropped
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::mentions;
+    ///
+    /// let result = mentions("@charlidamelio @Khaby.Lame @charlidamelio");
+    /// assert_eq!(result, vec!["@charlidamelio", "@Khaby.Lame"]);
+    /// ```
+    pub fn mentions(description: &str) -> Vec<String> {
+        dedup_in_order(iter_mentions(description))
     }
 
-    #[test]
-    fn test_tiktok_mentions_with_duplicates() {
-        let result = parse_mentions("@charlidamelio @charlidamelio @Khaby.Lame").unwrap();
-        let expected: HashSet<_> = ["@charlidamelio", "@Khaby.Lame"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    /// Extracts unique `#hashtags` from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., Instagram caption or Shorts comment)
+    ///
+    /// # Returns
+    /// A `Vec<String>` of unique hashtags.
+    ///
+    /// # Behavior
+    /// - Matches hashtags made of letters, digits and marks from any script, plus `_`, `-`, `.`
+    ///   (e.g., `#café`, `#日本語`, `#Москва`)
+    /// - Preserves original casing (e.g., `#Music` and `#music` both included if present)
+    /// - Returns hashtags in order of first appearance; later duplicates are dropped
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::hashtags;
+    ///
+    /// let result = hashtags("#fyp #CapCut #fyp");
+    /// assert_eq!(result, vec!["#fyp", "#CapCut"]);
+    /// ```
+    pub fn hashtags(description: &str) -> Vec<String> {
+        dedup_in_order(iter_hashtags(description))
     }
 
-    #[test]
-    fn test_mentions_case_insensitive_but_preserved() {
-        let result = parse_mentions("@AddisonRae @addisonrae").unwrap();
-        assert!(result.contains(&"@AddisonRae".to_string()));
-        assert!(result.contains(&"@addisonrae".to_string()));
-        assert_eq!(result.len(), 2); // preserves case
+    /// Extracts hashtags with a recommended set of clean-ups applied, as one opinionated default.
+    ///
+    /// # Arguments
+    /// - `description`: The raw input text (e.g., a caption pasted from anywhere)
+    ///
+    /// # Returns
+    /// The cleaned, unique hashtags, in order of first appearance.
+    ///
+    /// # Behavior
+    /// Equivalent to an [`Extractor`] with only hashtags enabled and these options:
+    /// - [`ExtractorBuilder::strip_invisible`]: zero-width characters and soft hyphens are
+    ///   removed first, so `#f\u{200B}yp` becomes `#fyp`
+    /// - [`ExtractorBuilder::ignore_in_urls`]: URL fragments such as `page#top` are skipped
+    /// - [`ExtractorBuilder::trim_trailing_punct`]: trailing `.` and `-` are trimmed
+    /// - [`ExtractorBuilder::require_alpha`]: tags without a letter (`#2024`, `#___`) are dropped
+    /// - [`ExtractorBuilder::max_len`] of [`MAX_TOKEN_LEN`]: tags over 140 characters are dropped
+    /// - `ExtractorBuilder::normalize_nfc`, only with the `unicode-normalization` feature:
+    ///   tags are converted to NFC
+    ///
+    /// Dedup stays exact and casing is kept. Build your own [`Extractor`] for other choices.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::sanitize_hashtags;
+    ///
+    /// let text = "#f\u{200B}yp. #2024 #Viral- https://site.com/#top #fyp";
+    /// assert_eq!(sanitize_hashtags(text), vec!["#fyp", "#Viral"]);
+    /// ```
+    pub fn sanitize_hashtags(description: &str) -> Vec<String> {
+        sanitizer().parse(description).hashtags
     }
 
-    #[test]
-    fn test_mentions_empty_input() {
-        let result = parse_mentions("").unwrap();
-        assert!(result.is_empty());
+    /// Returns the shared [`Extractor`] behind [`sanitize_hashtags`], building it on first use.
+    fn sanitizer() -> &'static Extractor {
+        #[cfg(feature = "std")]
+        static SANITIZER: std::sync::OnceLock<Extractor> = std::sync::OnceLock::new();
+        #[cfg(not(feature = "std"))]
+        static SANITIZER: once_cell::race::OnceBox<Extractor> = once_cell::race::OnceBox::new();
+        SANITIZER.get_or_init(|| {
+            let options = ExtractorBuilder::new()
+                .mentions(false)
+                .strip_invisible(true)
+                .ignore_in_urls(true)
+                .trim_trailing_punct(true)
+                .require_alpha(true)
+                .max_len(MAX_TOKEN_LEN);
+            #[cfg(feature = "unicode-normalization")]
+            let options = options.normalize_nfc(true);
+            let extractor = options.build().expect("default sanitize options are valid");
+            #[cfg(not(feature = "std"))]
+            let extractor = Box::new(extractor);
+            extractor
+        })
     }
 
-    // === Hashtags Tests ===
-    #[test]
-    fn test_tiktok_hashtags_trending() {
-        let result = parse_hashtags("#fyp #trending #CapCut #viral").unwrap();
-        let expected: HashSet<_> = ["#fyp", "#trending", "#CapCut", "#viral"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    /// How [`primary_hashtag`] picks the representative hashtag.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum PrimaryStrategy {
+        /// The first hashtag in the text.
+        #[default]
+        First,
+        /// The hashtag with the most occurrences.
+        MostFrequent,
+        /// The hashtag with the most characters.
+        Longest,
     }
 
-    #[test]
-    fn test_youtube_hashtags_mixed_case() {
-        let result = parse_hashtags("#Shorts #YouTubeShorts #Music #music").unwrap();
-        let expected: HashSet<_> = ["#Shorts", "#YouTubeShorts", "#Music", "#music"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    /// Picks a single representative hashtag, e.g. for a summary that shows only one tag.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being summarized)
+    /// - `strategy`: Which hashtag to pick
+    ///
+    /// # Returns
+    /// The chosen hashtag, or `None` if the text has none.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]; comparison is exact
+    /// - Ties go to the hashtag that appears first
+    /// - [`PrimaryStrategy::Longest`] counts characters (Unicode scalar values), not bytes
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{primary_hashtag, PrimaryStrategy};
+    ///
+    /// let text = "#fyp #dance #challenge #dance";
+    /// assert_eq!(primary_hashtag(text, PrimaryStrategy::First).as_deref(), Some("#fyp"));
+    /// assert_eq!(primary_hashtag(text, PrimaryStrategy::MostFrequent).as_deref(), Some("#dance"));
+    /// assert_eq!(primary_hashtag(text, PrimaryStrategy::Longest).as_deref(), Some("#challenge"));
+    /// assert_eq!(primary_hashtag("no tags", PrimaryStrategy::First), None);
+    /// ```
+    pub fn primary_hashtag(description: &str, strategy: PrimaryStrategy) -> Option<String> {
+        match strategy {
+            PrimaryStrategy::First => iter_hashtags(description).next().map(str::to_string),
+            PrimaryStrategy::MostFrequent => {
+                let counts = count_tokens(iter_hashtags(description));
+                let unique = dedup_in_order(iter_hashtags(description));
+                // `max_by_key` keeps the last maximum, so scanning in reverse keeps the first.
+                unique.into_iter().rev().max_by_key(|tag| counts[tag])
+            }
+            PrimaryStrategy::Longest => longest_hashtag(description),
+        }
     }
 
-    #[test]
-    fn test_hashtags_with_punctuation() {
-        let result = parse_hashtags("Try this! #Challenge-2025 #fun.time #go_crazy.").unwrap();
-        let expected: HashSet<_> = ["#Challenge-2025", "#fun.time", "#go_crazy."]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    /// Finds the hashtag with the most characters.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being analyzed)
+    ///
+    /// # Returns
+    /// The longest hashtag, or `None` if the text has none.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]
+    /// - Length is measured in Unicode scalar values, not bytes
+    /// - Ties go to the hashtag that appears first
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::longest_hashtag;
+    ///
+    /// assert_eq!(longest_hashtag("#fyp #café #dance").as_deref(), Some("#dance"));
+    /// assert_eq!(longest_hashtag("no tags"), None);
+    /// ```
+    pub fn longest_hashtag(description: &str) -> Option<String> {
+        extreme_by_len(iter_hashtags(description), |len, best| len > best).map(str::to_string)
     }
 
-    #[test]
-    fn test_hashtags_empty_input() {
-        let result = parse_hashtags("").unwrap();
-        assert!(result.is_empty());
+    /// Finds the hashtag with the fewest characters.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being analyzed)
+    ///
+    /// # Returns
+    /// The shortest hashtag, or `None` if the text has none.
+    ///
+    /// # Behavior
+    /// - Same matching, length and tie rules as [`longest_hashtag`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::shortest_hashtag;
+    ///
+    /// assert_eq!(shortest_hashtag("#dance #fyp #lol").as_deref(), Some("#fyp"));
+    /// ```
+    pub fn shortest_hashtag(description: &str) -> Option<String> {
+        extreme_by_len(iter_hashtags(description), |len, best| len < best).map(str::to_string)
     }
 
-    // === Combined Parser ===
-    #[test]
-    fn test_parse_both_mentions_and_hashtags() {
-        let result = parse_mentions_hashtags(
-            "@MrBeast just posted a new video! #fyp #MrBeastChallenge",
+    /// Finds the mention with the most characters.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment being analyzed)
+    ///
+    /// # Returns
+    /// The longest mention, or `None` if the text has none.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`], so e-mail addresses are skipped
+    /// - Same length and tie rules as [`longest_hashtag`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::longest_mention;
+    ///
+    /// assert_eq!(longest_mention("@a @MrBeast me@longaddress.com").as_deref(), Some("@MrBeast"));
+    /// ```
+    pub fn longest_mention(description: &str) -> Option<String> {
+        extreme_by_len(iter_mentions(description), |len, best| len > best).map(str::to_string)
+    }
+
+    /// Finds the mention with the fewest characters.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment being analyzed)
+    ///
+    /// # Returns
+    /// The shortest mention, or `None` if the text has none.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`], and the same length and tie rules as
+    ///   [`longest_hashtag`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::shortest_mention;
+    ///
+    /// assert_eq!(shortest_mention("@MrBeast @ab @cd").as_deref(), Some("@ab"));
+    /// ```
+    pub fn shortest_mention(description: &str) -> Option<String> {
+        extreme_by_len(iter_mentions(description), |len, best| len < best).map(str::to_string)
+    }
+
+    /// Extracts only the block of hashtags that ends the text, ignoring inline ones.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., `"great video!\n\n#fyp #viral"`)
+    ///
+    /// # Returns
+    /// The unique hashtags of the trailing block, in order of first appearance; empty if the
+    /// text doesn't end with a hashtag.
+    ///
+    /// # Behavior
+    /// - The block is the final run of hashtags separated only by whitespace, up to the end
+    ///   of the text (trailing whitespace is ignored)
+    /// - The block must start at the beginning of the text or after whitespace, so in
+    ///   `"word#a #b"` the glued-on `#a` is not part of it; tags written without spaces
+    ///   inside the block (`"text #a#b"`) still count
+    /// - Anything else ends the block, including a mention or emoji between or after the tags
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::trailing_hashtags;
+    ///
+    /// let caption = "my #mid take, great video!\n\n#fyp #viral #foryou\n";
+    /// assert_eq!(trailing_hashtags(caption), vec!["#fyp", "#viral", "#foryou"]);
+    /// assert!(trailing_hashtags("#fyp then text").is_empty());
+    /// ```
+    pub fn trailing_hashtags(description: &str) -> Vec<String> {
+        let matches: Vec<_> = hashtag_regex().find_iter(description).collect();
+        let mut end = description.trim_end().len();
+        let mut start = matches.len();
+        while start > 0 && matches[start - 1].end() == end {
+            start -= 1;
+            end = description[..matches[start].start()].trim_end().len();
+        }
+        while start < matches.len()
+            && !description[..matches[start].start()]
+                .chars()
+                .next_back()
+                .is_none_or(char::is_whitespace)
+        {
+            start += 1;
+        }
+        dedup_in_order(matches[start..].iter().map(|m| m.as_str()))
+    }
+
+    /// Extracts unique `@mentions`, allowing only the given extra characters in a username.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a tweet)
+    /// - `allowed`: Characters allowed in the username **in addition to** letters, digits and marks
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique mentions.
+    ///
+    /// # Behavior
+    /// - [`DEFAULT_TOKEN_CHARS`] (`"_-."`) reproduces [`parse_mentions`]
+    /// - Every character of `allowed` is passed through [`regex::escape`] before being placed in
+    ///   the character class, so `]`, `^`, `\` or `-` are treated literally and can never change
+    ///   the meaning of the pattern
+    /// - Deduplication and ordering match [`parse_mentions`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_with_charset;
+    ///
+    /// // Twitter handles: letters, digits and underscores only.
+    /// let result = parse_mentions_with_charset("@user.name @jack_dorsey", "_").unwrap();
+    /// assert_eq!(result, vec!["@user", "@jack_dorsey"]);
+    /// ```
+    pub fn parse_mentions_with_charset(
+        description: &str,
+        allowed: &str,
+    ) -> Result<Vec<String>, ParseError> {
+        let matches = Regex::new(&token_pattern('@', allowed))?;
+        Ok(dedup_in_order(
+            find_mentions(&matches, description).map(|m| m.as_str()),
+        ))
+    }
+
+    /// Extracts unique `#hashtags`, allowing only the given extra characters in a tag.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., Instagram caption or Shorts comment)
+    /// - `allowed`: Characters allowed in the tag **in addition to** letters, digits and marks
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique hashtags.
+    ///
+    /// # Behavior
+    /// - [`DEFAULT_TOKEN_CHARS`] (`"_-."`) reproduces [`parse_hashtags`]
+    /// - `allowed` is escaped the same way as in [`parse_mentions_with_charset`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_with_charset;
+    ///
+    /// let result = parse_hashtags_with_charset("#fun.time #rock&roll", "&").unwrap();
+    /// assert_eq!(result, vec!["#fun", "#rock&roll"]);
+    /// ```
+    pub fn parse_hashtags_with_charset(
+        description: &str,
+        allowed: &str,
+    ) -> Result<Vec<String>, ParseError> {
+        let matches = Regex::new(&token_pattern('#', allowed))?;
+        Ok(dedup_in_order(
+            matches.find_iter(description).map(|x| x.as_str()),
+        ))
+    }
+
+    /// Extracts unique `@mentions` with both their raw and bare forms.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., social media caption or comment)
+    ///
+    /// # Returns
+    /// A `Vec<Mention>` in the same order as [`mentions`].
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_rich;
+    ///
+    /// let found = parse_mentions_rich("cc @MrBeast");
+    /// assert_eq!(found[0].raw, "@MrBeast");
+    /// assert_eq!(found[0].handle, "MrBeast");
+    /// ```
+    pub fn parse_mentions_rich(description: &str) -> Vec<Mention> {
+        mentions(description)
+            .into_iter()
+            .map(|raw| Mention {
+                handle: without_sigil(&raw).to_string(),
+                raw,
+            })
+            .collect()
+    }
+
+    /// Extracts unique `#hashtags` with both their raw and bare forms.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., social media caption or comment)
+    ///
+    /// # Returns
+    /// A `Vec<Hashtag>` in the same order as [`hashtags`].
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_rich;
+    ///
+    /// let found = parse_hashtags_rich("new video #fyp");
+    /// assert_eq!(found[0].raw, "#fyp");
+    /// assert_eq!(found[0].tag, "fyp");
+    /// ```
+    pub fn parse_hashtags_rich(description: &str) -> Vec<Hashtag> {
+        hashtags(description)
+            .into_iter()
+            .map(|raw| Hashtag {
+                tag: without_sigil(&raw).to_string(),
+                raw,
+            })
+            .collect()
+    }
+
+    /// Extracts unique `@mentions`, reporting blank input separately.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a form field being validated)
+    ///
+    /// # Returns
+    /// [`ParseOutcome::Empty`] if `description` is empty or only whitespace, otherwise
+    /// [`ParseOutcome::Matches`] with the same list as [`mentions`] (possibly empty).
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_mentions_outcome, ParseOutcome};
+    ///
+    /// assert_eq!(parse_mentions_outcome("  "), ParseOutcome::Empty);
+    /// assert_eq!(parse_mentions_outcome("no tags"), ParseOutcome::Matches(vec![]));
+    /// assert_eq!(
+    ///     parse_mentions_outcome("hi @a"),
+    ///     ParseOutcome::Matches(vec!["@a".to_string()])
+    /// );
+    /// ```
+    pub fn parse_mentions_outcome(description: &str) -> ParseOutcome {
+        if description.trim().is_empty() {
+            ParseOutcome::Empty
+        } else {
+            ParseOutcome::Matches(mentions(description))
+        }
+    }
+
+    /// Extracts unique `#hashtags`, reporting blank input separately.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being validated)
+    ///
+    /// # Returns
+    /// [`ParseOutcome::Empty`] if `description` is empty or only whitespace, otherwise
+    /// [`ParseOutcome::Matches`] with the same list as [`hashtags`] (possibly empty).
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_hashtags_outcome, ParseOutcome};
+    ///
+    /// assert_eq!(parse_hashtags_outcome(""), ParseOutcome::Empty);
+    /// assert_eq!(parse_hashtags_outcome("a caption").into_vec(), Vec::<String>::new());
+    /// ```
+    pub fn parse_hashtags_outcome(description: &str) -> ParseOutcome {
+        if description.trim().is_empty() {
+            ParseOutcome::Empty
+        } else {
+            ParseOutcome::Matches(hashtags(description))
+        }
+    }
+
+    /// Extracts unique `$cashtags` (stock or crypto tickers) from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a finance-focused post)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique cashtags.
+    ///
+    /// # Behavior
+    /// - Thin wrapper around [`cashtags`]; never returns `Err`
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_cashtags;
+    ///
+    /// let result = parse_cashtags("$AAPL up, $TSLA down, paid $100").unwrap();
+    /// assert_eq!(result, vec!["$AAPL", "$TSLA"]);
+    /// ```
+    pub fn parse_cashtags(description: &str) -> Result<Vec<String>, ParseError> {
+        Ok(cashtags(description))
+    }
+
+    /// Extracts unique `$cashtags` (stock or crypto tickers) from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a finance-focused post)
+    ///
+    /// # Returns
+    /// A `Vec<String>` of unique cashtags.
+    ///
+    /// # Behavior
+    /// - The ticker must start with an ASCII letter, then may contain ASCII letters, digits, `.`
+    ///   and `-`
+    /// - Amounts like `$100` are **not** matched
+    /// - Preserves original casing and keeps first-seen order, like [`hashtags`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::cashtags;
+    ///
+    /// let result = cashtags("$btc $BRK.B $btc $9");
+    /// assert_eq!(result, vec!["$btc", "$BRK.B"]);
+    /// ```
+    pub fn cashtags(description: &str) -> Vec<String> {
+        dedup_in_order(cashtag_regex().find_iter(description).map(|c| c.as_str()))
+    }
+
+    /// Extracts unique e-mail addresses from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a bio with contact details)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique e-mail addresses.
+    ///
+    /// # Behavior
+    /// - Thin wrapper around [`emails`]; never returns `Err`
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_emails, parse_mentions};
+    ///
+    /// let text = "Collabs: team@studio.co.uk, or ping @studio";
+    /// assert_eq!(parse_emails(text).unwrap(), vec!["team@studio.co.uk"]);
+    /// assert_eq!(parse_mentions(text).unwrap(), vec!["@studio"]);
+    /// ```
+    pub fn parse_emails(description: &str) -> Result<Vec<String>, ParseError> {
+        Ok(emails(description))
+    }
+
+    /// Extracts unique e-mail addresses from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a bio with contact details)
+    ///
+    /// # Returns
+    /// A `Vec<String>` of unique `local@domain.tld` addresses.
+    ///
+    /// # Behavior
+    /// - The local part may contain letters, digits and `._%+-`; the domain needs at least one
+    ///   dot, so `user@localhost` is not matched
+    /// - A trailing sentence dot is not included (`mail a@b.com.` → `a@b.com`)
+    /// - Complements [`mentions`]: an `@` glued to a word is never a mention, so running both
+    ///   lets the caller decide what each `@` means
+    /// - Preserves original casing and keeps first-seen order, like [`mentions`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::emails;
+    ///
+    /// assert_eq!(emails("mail me: a.b+tag@mail.com."), vec!["a.b+tag@mail.com"]);
+    /// ```
+    pub fn emails(description: &str) -> Vec<String> {
+        dedup_in_order(email_regex().find_iter(description).map(|e| e.as_str()))
+    }
+
+    /// Extracts every `@mention` occurrence along with its byte span.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment shown in an editor)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec` of [`Span`]s, one per `@mention` occurrence.
+    ///
+    /// # Behavior
+    /// - Returns **every** occurrence in source order (no deduplication)
+    /// - Uses the same matching rules as [`parse_mentions`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_spans;
+    ///
+    /// let result = parse_mentions_spans("hi @MrBeast and @MrBeast").unwrap();
+    /// assert_eq!(result[0], ("@MrBeast".to_string(), 3, 11));
+    /// assert_eq!(result[1], ("@MrBeast".to_string(), 16, 24));
+    /// ```
+    pub fn parse_mentions_spans(description: &str) -> Result<Vec<Span>, ParseError> {
+        let matches = mention_regex();
+        Ok(find_mentions(matches, description)
+            .map(|m| (m.as_str().to_string(), m.start(), m.end()))
+            .collect())
+    }
+
+    /// Extracts every `#hashtag` occurrence along with its byte span.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., Instagram caption or Shorts comment)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec` of [`Span`]s, one per `#hashtag` occurrence.
+    ///
+    /// # Behavior
+    /// - Returns **every** occurrence in source order (no deduplication)
+    /// - Uses the same matching rules as [`parse_hashtags`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_spans;
+    ///
+    /// let result = parse_hashtags_spans("#fyp #viral").unwrap();
+    /// assert_eq!(result, vec![("#fyp".to_string(), 0, 4), ("#viral".to_string(), 5, 11)]);
+    /// ```
+    pub fn parse_hashtags_spans(description: &str) -> Result<Vec<Span>, ParseError> {
+        let matches = hashtag_regex();
+        Ok(matches
+            .find_iter(description)
+            .map(|x| (x.as_str().to_string(), x.start(), x.end()))
+            .collect())
+    }
+
+    /// Extracts unique `@mentions` with the leading `@` removed.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., TikTok or YouTube description)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique usernames without the sigil.
+    ///
+    /// # Behavior
+    /// - Same matching, deduplication and ordering as [`parse_mentions`]
+    /// - `@MrBeast` is returned as `MrBeast`
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_bare;
+    ///
+    /// let result = parse_mentions_bare("@MrBeast @Khaby.Lame @MrBeast").unwrap();
+    /// assert_eq!(result, vec!["MrBeast", "Khaby.Lame"]);
+    /// ```
+    pub fn parse_mentions_bare(description: &str) -> Result<Vec<String>, ParseError> {
+        Ok(parse_mentions(description)?
+            .iter()
+            .map(|m| without_sigil(m).to_string())
+            .collect())
+    }
+
+    /// Extracts unique `#hashtags` with the leading `#` removed.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., Instagram caption or Shorts comment)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique topics without the sigil.
+    ///
+    /// # Behavior
+    /// - Same matching, deduplication and ordering as [`parse_hashtags`]
+    /// - `#fyp` is returned as `fyp`
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_bare;
+    ///
+    /// let result = parse_hashtags_bare("#fyp #CapCut").unwrap();
+    /// assert_eq!(result, vec!["fyp", "CapCut"]);
+    /// ```
+    pub fn parse_hashtags_bare(description: &str) -> Result<Vec<String>, ParseError> {
+        Ok(parse_hashtags(description)?
+            .iter()
+            .map(|x| without_sigil(x).to_string())
+            .collect())
+    }
+
+    /// Extracts unique `@mentions`, dropping trailing `.` and `-` from each match.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., `"thanks @MrBeast."`)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique, trimmed mentions.
+    ///
+    /// # Behavior
+    /// - Strips any run of trailing `.` / `-` so a mention ending a sentence is clean
+    /// - Keeps interior punctuation (e.g., `@Khaby.Lame`)
+    /// - Drops tokens that are only the sigil plus punctuation (e.g., `@...`)
+    /// - Deduplicates **after** trimming, keeping first-seen order
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_trimmed;
+    ///
+    /// let result = parse_mentions_trimmed("thanks @MrBeast. and @Khaby.Lame!").unwrap();
+    /// assert_eq!(result, vec!["@MrBeast", "@Khaby.Lame"]);
+    /// ```
+    pub fn parse_mentions_trimmed(description: &str) -> Result<Vec<String>, ParseError> {
+        let matches = mention_regex();
+        Ok(dedup_in_order(
+            find_mentions(matches, description)
+                .filter_map(|m| trim_trailing_punctuation(m.as_str())),
+        ))
+    }
+
+    /// Extracts unique `#hashtags`, dropping trailing `.` and `-` from each match.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., `"Loved it #go_crazy."`)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique, trimmed hashtags.
+    ///
+    /// # Behavior
+    /// - Strips any run of trailing `.` / `-` so a hashtag ending a sentence is clean
+    /// - Keeps interior punctuation (e.g., `#fun.time`, `#Challenge-2025`)
+    /// - Drops tokens that are only the sigil plus punctuation (e.g., `#-.`)
+    /// - Deduplicates **after** trimming, keeping first-seen order
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_trimmed;
+    ///
+    /// let result = parse_hashtags_trimmed("#fun.time #go_crazy. #Challenge-2025.").unwrap();
+    /// assert_eq!(result, vec!["#fun.time", "#go_crazy", "#Challenge-2025"]);
+    /// ```
+    pub fn parse_hashtags_trimmed(description: &str) -> Result<Vec<String>, ParseError> {
+        let matches = hashtag_regex();
+        Ok(dedup_in_order(
+            matches
+                .find_iter(description)
+                .filter_map(|x| trim_trailing_punctuation(x.as_str())),
+        ))
+    }
+
+    /// Extracts `@mentions`, treating mentions that differ only by case as the same user.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., comments aggregated for analytics)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of mentions, unique ignoring case.
+    ///
+    /// # Behavior
+    /// - Lowercases each mention for the uniqueness check only
+    /// - Returns the casing of the **first** occurrence (e.g., `@AddisonRae @addisonrae` yields `@AddisonRae`)
+    /// - Use [`parse_mentions`] to keep differently-cased mentions as separate entries
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_dedup_ci;
+    ///
+    /// let result = parse_mentions_dedup_ci("@AddisonRae @addisonrae @MrBeast").unwrap();
+    /// assert_eq!(result, vec!["@AddisonRae", "@MrBeast"]);
+    /// ```
+    pub fn parse_mentions_dedup_ci(description: &str) -> Result<Vec<String>, ParseError> {
+        let matches = mention_regex();
+        Ok(dedup_in_order_by_key(
+            find_mentions(matches, description).map(|m| m.as_str()),
+            canonical_key,
+        ))
+    }
+
+    /// Extracts `#hashtags`, treating hashtags that differ only by case as the same topic.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., Instagram caption or Shorts comment)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of hashtags, unique ignoring case.
+    ///
+    /// # Behavior
+    /// - Lowercases each hashtag for the uniqueness check only
+    /// - Returns the casing of the **first** occurrence (e.g., `#Music #music` yields `#Music`)
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_dedup_ci;
+    ///
+    /// let result = parse_hashtags_dedup_ci("#Music #music #MUSIC #fyp").unwrap();
+    /// assert_eq!(result, vec!["#Music", "#fyp"]);
+    /// ```
+    pub fn parse_hashtags_dedup_ci(description: &str) -> Result<Vec<String>, ParseError> {
+        let matches = hashtag_regex();
+        Ok(dedup_in_order_by_key(
+            matches.find_iter(description).map(|x| x.as_str()),
+            canonical_key,
+        ))
+    }
+
+    /// The order of the tokens returned by [`Extractor::parse`], set with
+    /// [`ExtractorBuilder::sort`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum SortOrder {
+        /// No ordering guarantee; use when order does not matter. Currently the same as
+        /// [`SortOrder::Appearance`], but that may change.
+        None,
+        /// Order of first appearance in the text.
+        #[default]
+        Appearance,
+        /// Case-insensitive alphabetical order; tokens equal ignoring case are ordered by their
+        /// original casing (`#Rust` before `#rust`).
+        Alphabetical,
+    }
+
+    /// What [`ExtractorBuilder::dots`] does with Instagram-style mentions that have consecutive
+    /// dots (`@foo..bar`) or a trailing dot (`@foo.`).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum DotPolicy {
+        /// Keep such mentions as matched.
+        #[default]
+        Allow,
+        /// Drop such mentions entirely.
+        Reject,
+        /// Cut at the first `..` and strip trailing dots, so `@foo..bar` and `@foo.` both
+        /// become `@foo`.
+        Truncate,
+    }
+
+    /// Configures and builds an [`Extractor`].
+    ///
+    /// Every option defaults to the behavior of [`parse_mentions_hashtags`] with both kinds
+    /// enabled, so only the knobs you care about need to be set. The one exception is
+    /// [`ExtractorBuilder::trim_trailing_punct`], on by default: trailing `.` and `-` are
+    /// stripped, so `"#fyp."` yields `#fyp` where the free functions return `#fyp.`.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::Extractor;
+    ///
+    /// let extractor = Extractor::builder()
+    ///     .mentions(true)
+    ///     .hashtags(true)
+    ///     .trim_trailing_punct(true)
+    ///     .case_insensitive_dedup(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let result = extractor.parse("Thanks @AddisonRae and @addisonrae! #fyp.");
+    /// assert_eq!(result.mentions, vec!["@AddisonRae"]);
+    /// assert_eq!(result.hashtags, vec!["#fyp"]);
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct ExtractorBuilder {
+        mentions: bool,
+        hashtags: bool,
+        trim_trailing_punct: bool,
+        case_insensitive_dedup: bool,
+        mention_chars: String,
+        hashtag_chars: String,
+        min_len: Option<usize>,
+        max_len: Option<usize>,
+        min_distinct_chars: Option<usize>,
+        require_alpha: bool,
+        require_alphanumeric: bool,
+        no_leading_digit: bool,
+        limit: Option<usize>,
+        dots: DotPolicy,
+        normalize_case: bool,
+        sort: SortOrder,
+        ignore_in_urls: bool,
+        deny: HashSet<String>,
+        allow: Option<HashSet<String>>,
+        trim_chars: Vec<char>,
+        strip_invisible: bool,
+        require_boundary: bool,
+        max_scan_bytes: Option<usize>,
+        collapse_repeats: Option<usize>,
+        #[cfg(feature = "unicode-normalization")]
+        normalize_nfc: bool,
+        #[cfg(feature = "unicode-normalization")]
+        fold_ascii: bool,
+        input_is_html: bool,
+        dedup: bool,
+        collapse_substrings: bool,
+    }
+
+    impl Default for ExtractorBuilder {
+        fn default() -> Self {
+            Self {
+                mentions: true,
+                hashtags: true,
+                trim_trailing_punct: true,
+                case_insensitive_dedup: false,
+                mention_chars: DEFAULT_TOKEN_CHARS.to_string(),
+                hashtag_chars: DEFAULT_TOKEN_CHARS.to_string(),
+                min_len: None,
+                max_len: None,
+                min_distinct_chars: None,
+                require_alpha: false,
+                require_alphanumeric: false,
+                no_leading_digit: false,
+                limit: None,
+                dots: DotPolicy::Allow,
+                normalize_case: false,
+                sort: SortOrder::Appearance,
+                ignore_in_urls: false,
+                deny: HashSet::new(),
+                allow: None,
+                trim_chars: Vec::new(),
+                strip_invisible: false,
+                require_boundary: false,
+                max_scan_bytes: None,
+                collapse_repeats: None,
+                #[cfg(feature = "unicode-normalization")]
+                normalize_nfc: false,
+                #[cfg(feature = "unicode-normalization")]
+                fold_ascii: false,
+                input_is_html: false,
+                dedup: true,
+                collapse_substrings: false,
+            }
+        }
+    }
+
+    impl ExtractorBuilder {
+        /// Creates a builder with default options (same as [`Extractor::builder`]).
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Creates a builder for validating input: the defaults plus a [`MAX_TOKEN_LEN`] limit.
+        ///
+        /// Tokens longer than 140 characters are dropped, and reported as
+        /// [`RejectReason::TooLong`] by [`Extractor::validate`]. Other options can be chained
+        /// as usual.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::{ExtractorBuilder, MAX_TOKEN_LEN};
+        ///
+        /// let extractor = ExtractorBuilder::validating().build().unwrap();
+        /// let spam = format!("#{}", "a".repeat(MAX_TOKEN_LEN + 1));
+        /// assert_eq!(extractor.parse(&format!("#fyp {spam}")).hashtags, vec!["#fyp"]);
+        /// ```
+        pub fn validating() -> Self {
+            Self::default().max_len(MAX_TOKEN_LEN)
+        }
+
+        /// Whether to extract `@mentions` (default `true`).
+        pub fn mentions(mut self, enabled: bool) -> Self {
+            self.mentions = enabled;
+            self
+        }
+
+        /// Whether to extract `#hashtags` (default `true`).
+        pub fn hashtags(mut self, enabled: bool) -> Self {
+            self.hashtags = enabled;
+            self
+        }
+
+        /// Strip trailing `.` and `-` from each token, as [`parse_hashtags_trimmed`] does
+        /// (default `true`).
+        ///
+        /// This is the default trimming; [`ExtractorBuilder::trim_chars`] adds to it. Pass
+        /// `false` to keep tokens exactly as [`parse_mentions_hashtags`] returns them.
+        pub fn trim_trailing_punct(mut self, enabled: bool) -> Self {
+            self.trim_trailing_punct = enabled;
+            self
+        }
+
+        /// Strip any of `chars` from **both** ends of each token's body (default: none).
+        ///
+        /// This generalizes [`ExtractorBuilder::trim_trailing_punct`], which only strips `.`
+        /// and `-` from the end; both can be combined. The sigil is kept, so with `['_']`
+        /// `#__tag__` becomes `#tag`, and a token with nothing left is dropped.
+        ///
+        /// No extra characters are trimmed by default; the default trailing `.` / `-` trim
+        /// comes from [`ExtractorBuilder::trim_trailing_punct`].
+        pub fn trim_chars(mut self, chars: &[char]) -> Self {
+            self.trim_chars = chars.to_vec();
+            self
+        }
+
+        /// Remove zero-width characters (U+200B–U+200D and U+FEFF) and soft hyphens (U+00AD)
+        /// from the input before matching, so `#f\u{200B}yp` is found as `#fyp` (default
+        /// `false`).
+        ///
+        /// Spammers insert these to split a tag that still renders normally, and text copied
+        /// from word processors often carries soft hyphens inside words (`#foo\u{AD}bar`).
+        pub fn strip_invisible(mut self, enabled: bool) -> Self {
+            self.strip_invisible = enabled;
+            self
+        }
+
+        /// Treat the input as HTML: scan only the visible text (default `false`).
+        ///
+        /// Tags and comments are replaced by a space, so `href="#anchor"` is not a hashtag
+        /// and `<p>#a</p><p>b</p>` stays two words. Character references such as `&amp;`
+        /// and `&#39;` are decoded, so `it&#39;s` doesn't yield `#39`. This is a minimal
+        /// stripper, not a full HTML parser: `<script>` and `<style>` contents are scanned.
+        pub fn input_is_html(mut self, enabled: bool) -> Self {
+            self.input_is_html = enabled;
+            self
+        }
+
+        /// Drop repeated tokens, keeping the first occurrence (default `true`).
+        ///
+        /// With `false`, every match is returned in order, so `"#fyp #fyp #viral"` yields
+        /// `["#fyp", "#fyp", "#viral"]`, and [`ExtractorBuilder::limit`] counts occurrences.
+        /// [`ExtractorBuilder::case_insensitive_dedup`] then has no effect.
+        pub fn dedup(mut self, enabled: bool) -> Self {
+            self.dedup = enabled;
+            self
+        }
+
+        /// Drop a token when another returned token of the same kind extends it, so
+        /// `["#foryou", "#foryoupage"]` becomes `["#foryoupage"]` (default `false`).
+        ///
+        /// This is **prefix** matching, compared case-insensitively as [`canonical_key`] does:
+        /// `#ForYou` is dropped next to `#foryoupage`, but `#you` is kept because it only
+        /// appears in the middle. It runs after extraction, so with
+        /// [`ExtractorBuilder::limit`] fewer than `limit` tokens may be left. Meant for
+        /// tag-cloud clean-up; it compares every pair, so keep token lists small.
+        pub fn collapse_substrings(mut self, enabled: bool) -> Self {
+            self.collapse_substrings = enabled;
+            self
+        }
+
+        /// Treat tokens differing only by case as duplicates, keeping the first casing, as
+        /// [`parse_mentions_dedup_ci`] does (default `false`).
+        pub fn case_insensitive_dedup(mut self, enabled: bool) -> Self {
+            self.case_insensitive_dedup = enabled;
+            self
+        }
+
+        /// Extra characters allowed in a mention after letters and digits
+        /// (default [`DEFAULT_TOKEN_CHARS`]), escaped as in [`parse_mentions_with_charset`].
+        pub fn mention_chars(mut self, allowed: impl Into<String>) -> Self {
+            self.mention_chars = allowed.into();
+            self
+        }
+
+        /// Extra characters allowed in a hashtag after letters and digits
+        /// (default [`DEFAULT_TOKEN_CHARS`]), escaped as in [`parse_hashtags_with_charset`].
+        pub fn hashtag_chars(mut self, allowed: impl Into<String>) -> Self {
+            self.hashtag_chars = allowed.into();
+            self
+        }
+
+        /// Drop tokens whose body (the part after the sigil) has fewer than `len` characters
+        /// (default: no minimum).
+        ///
+        /// Length is measured in Unicode scalar values, so `#日本` has length 2.
+        pub fn min_len(mut self, len: usize) -> Self {
+            self.min_len = Some(len);
+            self
+        }
+
+        /// Drop tokens whose body (the part after the sigil) has more than `len` characters
+        /// (default: no maximum; [`MAX_TOKEN_LEN`] under [`ExtractorBuilder::validating`]).
+        ///
+        /// Length is measured in Unicode scalar values, like [`ExtractorBuilder::min_len`].
+        pub fn max_len(mut self, len: usize) -> Self {
+            self.max_len = Some(len);
+            self
+        }
+
+        /// Drop tokens whose body has fewer than `count` distinct characters (default: no
+        /// minimum).
+        ///
+        /// A cheap spam heuristic: with `2`, `#aaaa` and `#....` are dropped while `#ab` is
+        /// kept. Characters are compared exactly, so `#aA` has two.
+        pub fn min_distinct_chars(mut self, count: usize) -> Self {
+            self.min_distinct_chars = Some(count);
+            self
+        }
+
+        /// Drop tokens whose body contains no letter from any script, such as `#2025` or `@_._`
+        /// (default `false`).
+        pub fn require_alpha(mut self, enabled: bool) -> Self {
+            self.require_alpha = enabled;
+            self
+        }
+
+        /// Drop tokens whose body has no letter or digit at all, i.e. a sigil followed only by
+        /// punctuation such as `#___` or `@_._` (default `false`).
+        ///
+        /// Use this to reject underscore-only tags and usernames, which many platforms forbid,
+        /// while keeping everything else: `#a_b` and `#2025` are kept. Looser than
+        /// [`ExtractorBuilder::require_alpha`], which also drops `#2025`.
+        pub fn require_alphanumeric(mut self, enabled: bool) -> Self {
+            self.require_alphanumeric = enabled;
+            self
+        }
+
+        /// Drop tokens whose body starts with a digit, such as `#2025` or `#1direction`; digits
+        /// later on are fine (`#Challenge2025`) (default `false`).
+        ///
+        /// Any Unicode numeric character counts, including full-width digits.
+        pub fn no_leading_digit(mut self, enabled: bool) -> Self {
+            self.no_leading_digit = enabled;
+            self
+        }
+
+        /// Keep at most `limit` tokens of each kind (default: no limit).
+        ///
+        /// The limit counts **unique** tokens, after trimming, validation and deduplication, in
+        /// order of first appearance: `limit(30)` yields the first 30 distinct hashtags as
+        /// written. Scanning stops as soon as every enabled kind is full.
+        pub fn limit(mut self, limit: usize) -> Self {
+            self.limit = Some(limit);
+            self
+        }
+
+        /// Return every token lowercased, so `#Music` and `#music` both yield a single `#music`
+        /// (default `false`).
+        ///
+        /// Unlike [`ExtractorBuilder::case_insensitive_dedup`], which keeps the first casing,
+        /// this changes the output itself. Lowercasing is Unicode-aware (`#ÉTÉ` → `#été`).
+        pub fn normalize_case(mut self, enabled: bool) -> Self {
+            self.normalize_case = enabled;
+            self
+        }
+
+        /// The order of the returned tokens (default [`SortOrder::Appearance`]).
+        ///
+        /// Sorting happens after [`ExtractorBuilder::limit`] is applied, so `limit(5)` with
+        /// [`SortOrder::Alphabetical`] sorts the first five distinct tokens of the text.
+        pub fn sort(mut self, order: SortOrder) -> Self {
+            self.sort = order;
+            self
+        }
+
+        /// Only start a token at the beginning of the text or after whitespace, an opening
+        /// `(`, `[` or `{`, or an emoji (as in `🔥#fyp`) (default `false`).
+        ///
+        /// By default a hashtag may follow any character (`foo#bar` yields `#bar`) and a
+        /// mention anything but a letter, digit or `_`; with this option both need a boundary.
+        pub fn require_boundary(mut self, enabled: bool) -> Self {
+            self.require_boundary = enabled;
+            self
+        }
+
+        /// Scan at most the first `max` bytes of each input (default: no limit).
+        ///
+        /// A safety valve for untrusted input: a 50 MB caption costs no more than `max` bytes.
+        /// The cut is moved back to a character boundary. A token reaching the cut is discarded
+        /// if the next character of the input would have continued it; one that ends right
+        /// before a space or other separator is kept.
+        pub fn max_scan_bytes(mut self, max: usize) -> Self {
+            self.max_scan_bytes = Some(max);
+            self
+        }
+
+        /// Shorten runs of more than `max` identical characters to `max` (default: `None`,
+        /// keep tokens as written).
+        ///
+        /// With `Some(2)`, `#looooool` and `#loool` both become `#lool`, so stretched spellings
+        /// deduplicate into one tag. Characters are compared exactly, and `Some(0)` acts as
+        /// `Some(1)`. Length rules apply to the collapsed token.
+        pub fn collapse_repeats(mut self, max: Option<usize>) -> Self {
+            self.collapse_repeats = max;
+            self
+        }
+
+        /// Convert each token to Unicode Normalization Form C before dedup (default `false`).
+        ///
+        /// `#cafe\u{301}` (`e` + combining acute) then comes out as the precomposed `#café`,
+        /// so both encodings of a tag collapse into one. Needs the `unicode-normalization`
+        /// feature.
+        #[cfg(feature = "unicode-normalization")]
+        pub fn normalize_nfc(mut self, enabled: bool) -> Self {
+            self.normalize_nfc = enabled;
+            self
+        }
+
+        /// Ignore diacritics when deduplicating, keeping the first spelling (default `false`).
+        ///
+        /// `#café` and `#cafe` then count as one tag. Only the comparison key is folded: the
+        /// returned token is as written. Folding decomposes each character and drops the
+        /// combining marks, then maps Latin letters that have no decomposition through a
+        /// small table (`ß` → `ss`, `ø` → `o`, `ł` → `l`, `æ` → `ae`, `þ` → `th`, ...), so
+        /// Latin-script tags fold to ASCII. Other scripts are not transliterated: `#東京`
+        /// keys as itself. Needs the `unicode-normalization` feature.
+        #[cfg(feature = "unicode-normalization")]
+        pub fn fold_ascii(mut self, enabled: bool) -> Self {
+            self.fold_ascii = enabled;
+            self
+        }
+
+        /// Skip tokens that fall inside a URL, such as the `#section` fragment of
+        /// `https://example.com/page#section` or the `@user` in `https://x.com/@user`
+        /// (default `false`).
+        ///
+        /// A URL is a `scheme://` or `www.` prefix followed by everything up to the next
+        /// whitespace.
+        pub fn ignore_in_urls(mut self, enabled: bool) -> Self {
+            self.ignore_in_urls = enabled;
+            self
+        }
+
+        /// Drop tokens found in a denylist, compared case-insensitively (default: empty).
+        ///
+        /// Entries are usually given **without** the sigil (`"ad"`, `"sponsored"`) and then
+        /// match both `#ad` and `@ad`. An entry given with its sigil (`"#fyp"`) only matches
+        /// that kind. Entries are compared via [`canonical_key`], so case and full-width sigils
+        /// (`"＃ad"`) don't matter. Calling this again adds to the list.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::Extractor;
+        ///
+        /// let extractor = Extractor::builder().deny(["ad", "#fyp"]).build().unwrap();
+        /// let result = extractor.parse("@fyp #AD #fyp #cooking");
+        /// assert_eq!(result.mentions, vec!["@fyp"]);
+        /// assert_eq!(result.hashtags, vec!["#cooking"]);
+        /// ```
+        pub fn deny<I>(mut self, tokens: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: AsRef<str>,
+        {
+            self.deny
+                .extend(tokens.into_iter().map(|t| canonical_key(t.as_ref())));
+            self
+        }
+
+        /// Keep only tokens found in an allowlist, compared case-insensitively (default: off).
+        ///
+        /// Entries follow the same rules as [`ExtractorBuilder::deny`]: without a sigil they
+        /// match either kind, with one they match only that kind. Once set, an empty allowlist
+        /// yields no tokens at all. Calling this again adds to the list.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::Extractor;
+        ///
+        /// let campaign = ["#SummerSale", "#FreeShipping"];
+        /// let extractor = Extractor::builder().allow(campaign).build().unwrap();
+        /// let result = extractor.parse("#freeshipping on #shoes, #summersale ends soon");
+        /// assert_eq!(result.hashtags, vec!["#freeshipping", "#summersale"]);
+        /// ```
+        pub fn allow<I>(mut self, tokens: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: AsRef<str>,
+        {
+            self.allow
+                .get_or_insert_with(HashSet::new)
+                .extend(tokens.into_iter().map(|t| canonical_key(t.as_ref())));
+            self
+        }
+
+        /// How to handle mentions with consecutive or trailing dots (default
+        /// [`DotPolicy::Allow`]). Hashtags are not affected.
+        pub fn dots(mut self, policy: DotPolicy) -> Self {
+            self.dots = policy;
+            self
+        }
+
+        /// Compiles the configured pattern and returns a reusable [`Extractor`].
+        pub fn build(self) -> Result<Extractor, ParseError> {
+            let pattern = format!(
+                "{}|{}",
+                token_pattern('@', &self.mention_chars),
+                token_pattern('#', &self.hashtag_chars)
+            );
+            Ok(Extractor {
+                regex: Regex::new(&pattern)?,
+                options: self,
+            })
+        }
+    }
+
+    /// Why [`Extractor::validate`] rejected a token.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum RejectReason {
+        /// Nothing but the sigil was left after trimming.
+        NothingLeft,
+        /// The mention has consecutive or trailing dots under [`DotPolicy::Reject`].
+        BadDots,
+        /// Shorter than [`ExtractorBuilder::min_len`].
+        TooShort,
+        /// Longer than [`ExtractorBuilder::max_len`].
+        TooLong,
+        /// Too few distinct characters, under [`ExtractorBuilder::min_distinct_chars`].
+        TooRepetitive,
+        /// No letter, under [`ExtractorBuilder::require_alpha`].
+        NoLetter,
+        /// No letter or digit, under [`ExtractorBuilder::require_alphanumeric`].
+        NoLetterOrDigit,
+        /// Starts with a digit, under [`ExtractorBuilder::no_leading_digit`].
+        LeadingDigit,
+        /// Listed in [`ExtractorBuilder::deny`].
+        Denied,
+        /// Missing from [`ExtractorBuilder::allow`].
+        NotAllowed,
+    }
+
+    impl fmt::Display for RejectReason {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                RejectReason::NothingLeft => "nothing left after trimming",
+                RejectReason::BadDots => "consecutive or trailing dots",
+                RejectReason::TooShort => "too short",
+                RejectReason::TooLong => "too long",
+                RejectReason::TooRepetitive => "too few distinct characters",
+                RejectReason::NoLetter => "no letter",
+                RejectReason::NoLetterOrDigit => "no letter or digit",
+                RejectReason::LeadingDigit => "starts with a digit",
+                RejectReason::Denied => "denied",
+                RejectReason::NotAllowed => "not in the allowlist",
+            })
+        }
+    }
+
+    /// A token rejected by [`Extractor::validate`], as it appeared in the text.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Rejected {
+        /// The token as matched, before any trimming.
+        pub token: String,
+        /// The first rule it failed.
+        pub reason: RejectReason,
+    }
+
+    /// Tokens of one kind gathered by [`Extractor::parse`], deduplicated as they arrive.
+    #[derive(Default)]
+    struct Collected<'a> {
+        seen: HashSet<Cow<'a, str>>,
+        tokens: Vec<Cow<'a, str>>,
+    }
+
+    /// What [`Extractor::parse_cow`] returns: [`MentionsHashtags`] with tokens that borrow
+    /// from the input where possible.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct MentionsHashtagsCow<'a> {
+        /// The mentions, borrowed unless an option rewrote them.
+        pub mentions: Vec<Cow<'a, str>>,
+        /// The hashtags, borrowed unless an option rewrote them.
+        pub hashtags: Vec<Cow<'a, str>>,
+    }
+
+    impl MentionsHashtagsCow<'_> {
+        /// Converts into an owned [`MentionsHashtags`], copying the borrowed tokens.
+        pub fn into_owned(self) -> MentionsHashtags {
+            MentionsHashtags {
+                mentions: self.mentions.into_iter().map(Cow::into_owned).collect(),
+                hashtags: self.hashtags.into_iter().map(Cow::into_owned).collect(),
+            }
+        }
+    }
+
+    /// A configured, precompiled mention and hashtag extractor.
+    ///
+    /// The regex is compiled once in [`ExtractorBuilder::build`]; [`Extractor::parse`] can then
+    /// be called any number of times.
+    ///
+    /// `Extractor` is `Send + Sync` and `parse` takes `&self`, so one instance can be stored in
+    /// shared server state (directly or in an `Arc`) and used from many threads at once.
+    /// Cloning is cheap too: `Regex` shares its compiled program internally, so only the
+    /// options are copied.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::Extractor;
+    /// use std::sync::Arc;
+    ///
+    /// let extractor = Arc::new(Extractor::builder().build().unwrap());
+    /// let worker = {
+    ///     let extractor = Arc::clone(&extractor);
+    ///     std::thread::spawn(move || extractor.parse("#from_a_thread").hashtags)
+    /// };
+    /// assert_eq!(worker.join().unwrap(), vec!["#from_a_thread"]);
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct Extractor {
+        regex: Regex,
+        options: ExtractorBuilder,
+    }
+
+    impl Extractor {
+        /// Starts configuring a new extractor.
+        pub fn builder() -> ExtractorBuilder {
+            ExtractorBuilder::new()
+        }
+
+        /// Parses the given description using the configured options.
+        ///
+        /// # Arguments
+        /// - `description`: The input text (e.g., social media caption or comment)
+        ///
+        /// # Returns
+        /// A `MentionsHashtags` struct with the tokens of each enabled kind, unique and in
+        /// order of first appearance.
+        pub fn parse(&self, description: &str) -> MentionsHashtags {
+            self.run(description, |_, _| {}).into_owned()
+        }
+
+        /// Parses like [`Extractor::parse`], borrowing every token the options left unchanged.
+        ///
+        /// # Arguments
+        /// - `description`: The input text (e.g., social media caption or comment)
+        ///
+        /// # Returns
+        /// A [`MentionsHashtagsCow`] whose tokens are `Cow::Borrowed` slices of `description`,
+        /// except those that had to be rebuilt (e.g. lowercased by
+        /// [`ExtractorBuilder::normalize_case`] or trimmed at the front by
+        /// [`ExtractorBuilder::trim_chars`]). Trimming only the end still borrows.
+        ///
+        /// # Behavior
+        /// - When [`ExtractorBuilder::input_is_html`] or [`ExtractorBuilder::strip_invisible`]
+        ///   actually rewrite the input, the tokens come from the rewritten text and are owned
+        ///
+        /// # Examples
+        /// ```
+        /// use std::borrow::Cow;
+        /// use mentions_hashtags::mentions_hashtags::Extractor;
+        ///
+        /// let extractor = Extractor::builder().trim_chars(&['_']).build().unwrap();
+        /// let result = extractor.parse_cow("#fyp #_tag_");
+        /// assert!(matches!(result.hashtags[0], Cow::Borrowed("#fyp")));
+        /// assert!(matches!(result.hashtags[1], Cow::Owned(ref tag) if tag == "#tag"));
+        /// ```
+        pub fn parse_cow<'a>(&self, description: &'a str) -> MentionsHashtagsCow<'a> {
+            self.run(description, |_, _| {})
+        }
+
+        /// Parses like [`Extractor::parse`], and also reports the tokens that were rejected.
+        ///
+        /// # Arguments
+        /// - `description`: The input text (e.g., a caption being linted)
+        ///
+        /// # Returns
+        /// The accepted tokens, exactly as [`Extractor::parse`] returns them, plus one
+        /// [`Rejected`] entry per distinct rejected token, in order of first appearance.
+        ///
+        /// # Behavior
+        /// - A token is rejected when trimming leaves nothing, it fails the dot policy, or it
+        ///   fails a validation rule; the first failing rule is reported
+        /// - Tokens that are merely skipped (duplicates, over the limit, inside a URL, without
+        ///   a boundary, or cut off by the scan budget) are not reported
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::{Extractor, RejectReason};
+        ///
+        /// let extractor = Extractor::builder().max_len(10).no_leading_digit(true).build().unwrap();
+        /// let (accepted, rejected) = extractor.validate("#ok #2025 #waytoolongforthis");
+        /// assert_eq!(accepted.hashtags, vec!["#ok"]);
+        /// assert_eq!(rejected[0].token, "#2025");
+        /// assert_eq!(rejected[0].reason, RejectReason::LeadingDigit);
+        /// assert_eq!(rejected[1].reason, RejectReason::TooLong);
+        /// ```
+        pub fn validate(&self, description: &str) -> (MentionsHashtags, Vec<Rejected>) {
+            let mut seen = HashSet::new();
+            let mut rejected = Vec::new();
+            let accepted = self.run(description, |token, reason| {
+                if seen.insert(token.to_string()) {
+                    rejected.push(Rejected {
+                        token: token.to_string(),
+                        reason,
+                    });
+                }
+            });
+            (accepted.into_owned(), rejected)
+        }
+
+        /// Shared body of [`Extractor::parse`], [`Extractor::parse_cow`] and
+        /// [`Extractor::validate`]; `on_reject` gets each rejected match with the reason.
+        fn run<'a>(
+            &self,
+            description: &'a str,
+            mut on_reject: impl FnMut(&str, RejectReason),
+        ) -> MentionsHashtagsCow<'a> {
+            let mut mentions_hashtags = MentionsHashtagsCow::default();
+
+            if !self.options.mentions && !self.options.hashtags {
+                return mentions_hashtags;
+            }
+
+            // `beyond` is the unscanned rest of the input, if the scan budget cut it.
+            let (description, beyond) = match self.options.max_scan_bytes {
+                Some(max) if description.len() > max => {
+                    let cut = floor_char_boundary(description, max);
+                    (&description[..cut], Some(&description[cut..]))
+                }
+                _ => (description, None),
+            };
+            let mut text = Cow::Borrowed(description);
+            if self.options.input_is_html {
+                if let Cow::Owned(visible) = html_to_text(&text) {
+                    text = Cow::Owned(visible);
+                }
+            }
+            if self.options.strip_invisible {
+                if let Cow::Owned(cleaned) = remove_invisible(&text) {
+                    text = Cow::Owned(cleaned);
+                }
+            }
+            // Tokens can only borrow from the caller's text if it wasn't rewritten.
+            let original = match text {
+                Cow::Borrowed(original) => Some(original),
+                Cow::Owned(_) => None,
+            };
+            let description = &*text;
+
+            let mut mentions = Collected::default();
+            let mut hashtags = Collected::default();
+            let mut urls = self
+                .options
+                .ignore_in_urls
+                .then(|| url_regex().find_iter(description))
+                .into_iter()
+                .flatten()
+                .peekable();
+            for m in find_tokens(&self.regex, description) {
+                let mentions_done = !self.options.mentions || !self.has_room(&mentions);
+                let hashtags_done = !self.options.hashtags || !self.has_room(&hashtags);
+                if mentions_done && hashtags_done {
+                    break;
+                }
+                let is_mention = is_mention_token(m.as_str());
+                if (is_mention && mentions_done) || (!is_mention && hashtags_done) {
+                    continue;
+                }
+
+                // Both iterators run in text order, so URLs ending before this token are done.
+                while urls.next_if(|url| url.end() <= m.start()).is_some() {}
+                if urls.peek().is_some_and(|url| url.start() <= m.start()) {
+                    continue;
+                }
+                if self.options.require_boundary && !is_boundary_before(description, m.start()) {
+                    continue;
+                }
+                if m.end() == description.len()
+                    && beyond.is_some_and(|beyond| self.continues_into(m.as_str(), beyond))
+                {
+                    continue;
+                }
+
+                let token = match self.normalize(m.as_str()) {
+                    Ok(token) => token,
+                    Err(reason) => {
+                        on_reject(m.as_str(), reason);
+                        continue;
+                    }
+                };
+                if let Err(reason) = self.check(&token) {
+                    on_reject(m.as_str(), reason);
+                    continue;
+                }
+                // A borrowed token is always a prefix of its match.
+                let token = match (token, original) {
+                    (Cow::Borrowed(token), Some(original)) => {
+                        Cow::Borrowed(&original[m.start()..m.start() + token.len()])
+                    }
+                    (token, _) => Cow::Owned(token.into_owned()),
+                };
+                if is_mention {
+                    self.collect(&mut mentions, token);
+                } else {
+                    self.collect(&mut hashtags, token);
+                }
+            }
+            mentions_hashtags.mentions = mentions.tokens;
+            mentions_hashtags.hashtags = hashtags.tokens;
+            if self.options.collapse_substrings {
+                drop_extended_prefixes(&mut mentions_hashtags.mentions);
+                drop_extended_prefixes(&mut mentions_hashtags.hashtags);
+            }
+            if self.options.sort == SortOrder::Alphabetical {
+                sort_alphabetically(&mut mentions_hashtags.mentions);
+                sort_alphabetically(&mut mentions_hashtags.hashtags);
+            }
+
+            mentions_hashtags
+        }
+
+        /// Applies the configured per-token clean-up, failing if the dot policy rejects the
+        /// token or nothing is left.
+        fn normalize<'a>(&self, token: &'a str) -> Result<Cow<'a, str>, RejectReason> {
+            let token = if self.options.trim_trailing_punct {
+                trim_trailing_punctuation(token).ok_or(RejectReason::NothingLeft)?
+            } else {
+                token
+            };
+            let token = if is_mention_token(token) {
+                apply_dot_policy(token, self.options.dots).ok_or(RejectReason::BadDots)?
+            } else {
+                token
+            };
+            let token =
+                trim_body(token, &self.options.trim_chars).ok_or(RejectReason::NothingLeft)?;
+            #[cfg(feature = "unicode-normalization")]
+            let token = if self.options.normalize_nfc {
+                to_nfc(token)
+            } else {
+                token
+            };
+            Ok(match self.options.collapse_repeats {
+                Some(max) => collapse_runs(token, max.max(1)),
+                None => token,
+            })
+        }
+
+        /// Checks a normalized token against the configured validation rules, returning the
+        /// first one it fails.
+        fn check(&self, token: &str) -> Result<(), RejectReason> {
+            let body = without_sigil(token);
+            let len = body.chars().count();
+            let options = &self.options;
+            if options.min_len.is_some_and(|min| len < min) {
+                Err(RejectReason::TooShort)
+            } else if options.max_len.is_some_and(|max| len > max) {
+                Err(RejectReason::TooLong)
+            } else if options
+                .min_distinct_chars
+                .is_some_and(|min| !has_distinct_chars(body, min))
+            {
+                Err(RejectReason::TooRepetitive)
+            } else if options.require_alpha && !body.chars().any(char::is_alphabetic) {
+                Err(RejectReason::NoLetter)
+            } else if options.require_alphanumeric && !body.chars().any(char::is_alphanumeric) {
+                Err(RejectReason::NoLetterOrDigit)
+            } else if options.no_leading_digit && body.starts_with(char::is_numeric) {
+                Err(RejectReason::LeadingDigit)
+            } else if in_token_list(&options.deny, token) {
+                Err(RejectReason::Denied)
+            } else if options
+                .allow
+                .as_ref()
+                .is_some_and(|allow| !in_token_list(allow, token))
+            {
+                Err(RejectReason::NotAllowed)
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Whether `token`, matched at the end of the scanned text, would have been longer had
+        /// the scan gone on into `beyond`.
+        fn continues_into(&self, token: &str, beyond: &str) -> bool {
+            let Some(next) = beyond.chars().next() else {
+                return false;
+            };
+            let extended = format!("{token}{next}");
+            self.regex
+                .find(&extended)
+                .is_some_and(|m| m.start() == 0 && m.end() > token.len())
+        }
+
+        /// Whether `collected` may take another token under the configured limit.
+        fn has_room(&self, collected: &Collected<'_>) -> bool {
+            self.options
+                .limit
+                .is_none_or(|limit| collected.tokens.len() < limit)
+        }
+
+        /// Adds `token` unless dedup is on and it duplicates an earlier one under the configured
+        /// case rules.
+        fn collect<'a>(&self, collected: &mut Collected<'a>, token: Cow<'a, str>) {
+            let token = if self.options.normalize_case {
+                to_lowercase(token)
+            } else {
+                token
+            };
+            if self.options.dedup {
+                let key = if self.options.normalize_case || self.options.case_insensitive_dedup {
+                    Cow::Owned(canonical_key(&token))
+                } else {
+                    token.clone()
+                };
+                #[cfg(feature = "unicode-normalization")]
+                let key = if self.options.fold_ascii {
+                    fold_diacritics(key)
+                } else {
+                    key
+                };
+                if !collected.seen.insert(key) {
+                    return;
+                }
+            }
+            collected.tokens.push(token);
+        }
+    }
+
+    /// Counts how many times each `@mention` occurs in the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment thread)
+    ///
+    /// # Returns
+    /// A `Result` containing a `HashMap` from each mention to its number of occurrences.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`]; counting is case-sensitive
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::count_mentions;
+    ///
+    /// let counts = count_mentions("@MrBeast @MrBeast @Khaby.Lame").unwrap();
+    /// assert_eq!(counts["@MrBeast"], 2);
+    /// assert_eq!(counts["@Khaby.Lame"], 1);
+    /// ```
+    pub fn count_mentions(description: &str) -> Result<HashMap<String, usize>, ParseError> {
+        Ok(count_tokens(iter_mentions(description)))
+    }
+
+    /// Counts how many times each `#hashtag` occurs in the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption used for trending analysis)
+    ///
+    /// # Returns
+    /// A `Result` containing a `HashMap` from each hashtag to its number of occurrences.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]; counting is case-sensitive
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::count_hashtags;
+    ///
+    /// let counts = count_hashtags("#fyp #fyp #viral").unwrap();
+    /// assert_eq!(counts["#fyp"], 2);
+    /// assert_eq!(counts["#viral"], 1);
+    /// ```
+    pub fn count_hashtags(description: &str) -> Result<HashMap<String, usize>, ParseError> {
+        Ok(count_tokens(iter_hashtags(description)))
+    }
+
+    /// Counts every `@mention` occurrence in the input text, duplicates included.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment checked by a spam filter)
+    ///
+    /// # Returns
+    /// The total number of mentions.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`]
+    /// - Allocates nothing; this is the cheapest way to enforce a "too many mentions" limit
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::count_mentions_total;
+    ///
+    /// assert_eq!(count_mentions_total("@a @b @a me@mail.com"), 3);
+    /// ```
+    pub fn count_mentions_total(description: &str) -> usize {
+        iter_mentions(description).count()
+    }
+
+    /// Counts every `#hashtag` occurrence in the input text, duplicates included.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption checked against a hashtag limit)
+    ///
+    /// # Returns
+    /// The total number of hashtags.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]
+    /// - Allocates nothing, like [`count_mentions_total`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::count_hashtags_total;
+    ///
+    /// assert_eq!(count_hashtags_total("#fyp #fyp #viral"), 3);
+    /// ```
+    pub fn count_hashtags_total(description: &str) -> usize {
+        iter_hashtags(description).count()
+    }
+
+    /// Counts the distinct mentions and hashtags together, e.g. as a "tag richness" metric.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being scored)
+    ///
+    /// # Returns
+    /// The number of distinct tokens. `@foo` and `#foo` count as two, since their sigils
+    /// differ; case matters, as in [`parse_mentions_hashtags`].
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::unique_token_count;
+    ///
+    /// assert_eq!(unique_token_count("@foo #foo #fyp #fyp @foo"), 3);
+    /// ```
+    pub fn unique_token_count(description: &str) -> usize {
+        let mut seen = HashSet::new();
+        visit_tokens(description, |_, token, _, _| {
+            seen.insert(token);
+        });
+        seen.len()
+    }
+
+    /// Measures how much of the input text is made of hashtags.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being scored for spam)
+    ///
+    /// # Returns
+    /// Characters inside hashtag matches divided by all characters, from `0.0` to `1.0`.
+    /// Empty input returns `0.0`.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]; every occurrence counts, duplicates included
+    /// - Counts Unicode scalar values, not bytes, and whitespace counts toward the total
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::hashtag_char_ratio;
+    ///
+    /// assert_eq!(hashtag_char_ratio("#ab cd"), 0.5);
+    /// assert!(hashtag_char_ratio("#follow #like #f4f #spam") > 0.8);
+    /// ```
+    pub fn hashtag_char_ratio(description: &str) -> f64 {
+        let total = description.chars().count();
+        if total == 0 {
+            return 0.0;
+        }
+        let tagged: usize = iter_hashtags(description).map(|h| h.chars().count()).sum();
+        tagged as f64 / total as f64
+    }
+
+    /// Formats the unique mentions as lines of text, for piping into other tools.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption read from stdin)
+    ///
+    /// # Returns
+    /// The tokens of [`mentions`], each followed by `\n`; an empty string if there are none.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::mentions_to_lines;
+    ///
+    /// assert_eq!(mentions_to_lines("@a @b @a"), "@a\n@b\n");
+    /// assert_eq!(mentions_to_lines("no mentions"), "");
+    /// ```
+    pub fn mentions_to_lines(description: &str) -> String {
+        to_lines(mentions(description))
+    }
+
+    /// Formats the unique hashtags as lines of text, for piping into other tools.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption read from stdin)
+    ///
+    /// # Returns
+    /// The tokens of [`hashtags`], each followed by `\n`; an empty string if there are none.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::hashtags_to_lines;
+    ///
+    /// assert_eq!(hashtags_to_lines("#fyp #viral"), "#fyp\n#viral\n");
+    /// ```
+    pub fn hashtags_to_lines(description: &str) -> String {
+        to_lines(hashtags(description))
+    }
+
+    /// Key under which [`group_hashtags_by_initial`] buckets hashtags that don't start with a
+    /// letter (e.g. `#2024`, `#_tag`).
+    pub const NON_LETTER_INITIAL: char = '#';
+
+    /// Groups the unique hashtags by the first character of their body, for an A–Z index.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being indexed)
+    ///
+    /// # Returns
+    /// A map from the lowercased initial to the hashtags starting with it, sorted by key.
+    ///
+    /// # Behavior
+    /// - Same tokens as [`hashtags`]; each bucket keeps order of first appearance
+    /// - Hashtags starting with a digit or `_` go under [`NON_LETTER_INITIAL`]
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::group_hashtags_by_initial;
+    ///
+    /// let groups = group_hashtags_by_initial("#fyp #CapCut #fun #2024");
+    /// assert_eq!(groups[&'f'], vec!["#fyp", "#fun"]);
+    /// assert_eq!(groups[&'c'], vec!["#CapCut"]);
+    /// assert_eq!(groups[&'#'], vec!["#2024"]);
+    /// ```
+    pub fn group_hashtags_by_initial(description: &str) -> BTreeMap<char, Vec<String>> {
+        let mut groups: BTreeMap<char, Vec<String>> = BTreeMap::new();
+        for hashtag in hashtags(description) {
+            let initial = without_sigil(&hashtag)
+                .chars()
+                .next()
+                .filter(|c| c.is_alphabetic())
+                .and_then(|c| c.to_lowercase().next())
+                .unwrap_or(NON_LETTER_INITIAL);
+            groups.entry(initial).or_default().push(hashtag);
+        }
+        groups
+    }
+
+    /// Whether the input text contains at least one `@mention`.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a feed item being filtered)
+    ///
+    /// # Returns
+    /// `true` as soon as the first mention is found.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`], so an e-mail address alone is not a mention
+    /// - Stops scanning at the first match and allocates nothing
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::has_mention;
+    ///
+    /// assert!(has_mention("cc @MrBeast"));
+    /// assert!(!has_mention("mail me@example.com"));
+    /// ```
+    pub fn has_mention(description: &str) -> bool {
+        iter_mentions(description).next().is_some()
+    }
+
+    /// Whether the input text contains at least one `#hashtag`.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a feed item being filtered)
+    ///
+    /// # Returns
+    /// `true` as soon as the first hashtag is found.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]
+    /// - Uses `Regex::is_match`, the cheapest possible check
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::has_hashtag;
+    ///
+    /// assert!(has_hashtag("new video #fyp"));
+    /// assert!(!has_hashtag("no tags, just a # sign"));
+    /// ```
+    pub fn has_hashtag(description: &str) -> bool {
+        hashtag_regex().is_match(description)
+    }
+
+    /// Lazily yields every `@mention` in the input text, borrowing from it.
+    ///
+    /// # Arguments
+    /// - `description`: The input text
+    ///
+    /// # Returns
+    /// An iterator of `&str` slices into `description`, one per occurrence.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`mentions`], but **no** deduplication and no allocation
+    /// - Matches are found on demand, so stopping early skips the rest of the text
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::iter_mentions;
+    ///
+    /// let first = iter_mentions("@a @b @a").next();
+    /// assert_eq!(first, Some("@a"));
+    /// assert_eq!(iter_mentions("@a @b @a").collect::<Vec<_>>(), vec!["@a", "@b", "@a"]);
+    /// ```
+    pub fn iter_mentions(description: &str) -> impl Iterator<Item = &str> {
+        find_mentions(mention_regex(), description).map(|m| m.as_str())
+    }
+
+    /// Lazily yields every `#hashtag` in the input text, borrowing from it.
+    ///
+    /// # Arguments
+    /// - `description`: The input text
+    ///
+    /// # Returns
+    /// An iterator of `&str` slices into `description`, one per occurrence.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`hashtags`], but **no** deduplication and no allocation
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::iter_hashtags;
+    ///
+    /// let long: Vec<&str> = iter_hashtags("#fyp #trending #ad").filter(|t| t.len() > 4).collect();
+    /// assert_eq!(long, vec!["#trending"]);
+    /// ```
+    pub fn iter_hashtags(description: &str) -> impl Iterator<Item = &str> {
+        hashtag_regex().find_iter(description).map(|x| x.as_str())
+    }
+
+    /// Rewrites every `@mention` and `#hashtag` in the text as an HTML link.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment about to be rendered)
+    /// - `mention_url`: Builds the link target for a mention; receives the bare handle (`MrBeast`)
+    /// - `hashtag_url`: Builds the link target for a hashtag; receives the bare tag (`fyp`)
+    ///
+    /// # Returns
+    /// The text with each token replaced by `<a href="URL">TOKEN</a>`; everything else is copied
+    /// through unchanged.
+    ///
+    /// # Behavior
+    /// - Tokens are rewritten in place and in order; duplicates are linked every time
+    /// - Same matching rules as [`mentions`] and [`hashtags`] (so emails are left alone)
+    /// - **No HTML escaping is performed.** The surrounding text and the URLs returned by the
+    ///   closures are inserted verbatim, so escape untrusted input before calling this and make
+    ///   sure the closures return attribute-safe URLs
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::linkify;
+    ///
+    /// let html = linkify(
+    ///     "@MrBeast posted #fyp",
+    ///     |user| format!("/u/{user}"),
+    ///     |tag| format!("/t/{tag}"),
+    /// );
+    /// assert_eq!(
+    ///     html,
+    ///     r#"<a href="/u/MrBeast">@MrBeast</a> posted <a href="/t/fyp">#fyp</a>"#
+    /// );
+    /// ```
+    pub fn linkify(
+        description: &str,
+        mention_url: impl Fn(&str) -> String,
+        hashtag_url: impl Fn(&str) -> String,
+    ) -> String {
+        let mut linked = String::with_capacity(description.len());
+        let mut last = 0;
+        for m in find_tokens(token_regex(), description) {
+            let token = m.as_str();
+            let url = if is_mention_token(token) {
+                mention_url(without_sigil(token))
+            } else {
+                hashtag_url(without_sigil(token))
+            };
+            linked.push_str(&description[last..m.start()]);
+            linked.push_str(&format!(r#"<a href="{url}">{token}</a>"#));
+            last = m.end();
+        }
+        linked.push_str(&description[last..]);
+        linked
+    }
+
+    /// Removes `@mentions` and/or `#hashtags` from the text, leaving the plain caption.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption to feed into a sentiment model)
+    /// - `mentions`: Whether to remove `@mentions`
+    /// - `hashtags`: Whether to remove `#hashtags`
+    ///
+    /// # Returns
+    /// The remaining text with tokens removed.
+    ///
+    /// # Behavior
+    /// - Every whitespace run left behind (including newlines) collapses to a single space
+    /// - Leading and trailing whitespace is trimmed
+    /// - Same matching rules as [`mentions`] and [`hashtags`] (so emails are kept)
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::strip;
+    ///
+    /// assert_eq!(strip("@MrBeast new video #fyp #viral", true, true), "new video");
+    /// assert_eq!(strip("@MrBeast new video #fyp", false, true), "@MrBeast new video");
+    /// ```
+    pub fn strip(description: &str, mentions: bool, hashtags: bool) -> String {
+        let mut remaining = String::with_capacity(description.len());
+        let mut last = 0;
+        for m in find_tokens(token_regex(), description) {
+            let remove = if is_mention_token(m.as_str()) {
+                mentions
+            } else {
+                hashtags
+            };
+            if remove {
+                remaining.push_str(&description[last..m.start()]);
+                remaining.push(' ');
+                last = m.end();
+            }
+        }
+        remaining.push_str(&description[last..]);
+        collapse_whitespace(&remaining)
+    }
+
+    /// Splits the input text into its tokens and the plain text left over, in one pass.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption being tokenized)
+    ///
+    /// # Returns
+    /// A `(MentionsHashtags, String)` pair: the unique tokens, as from
+    /// `parse_mentions_hashtags(description, true, true)`, and the text with every token
+    /// removed, as from `strip(description, true, true)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::split_tags;
+    ///
+    /// let (tags, text) = split_tags("@MrBeast new video #fyp #viral");
+    /// assert_eq!(tags.mentions, vec!["@MrBeast"]);
+    /// assert_eq!(tags.hashtags, vec!["#fyp", "#viral"]);
+    /// assert_eq!(text, "new video");
+    /// ```
+    pub fn split_tags(description: &str) -> (MentionsHashtags, String) {
+        let mut found_mentions = Vec::new();
+        let mut found_hashtags = Vec::new();
+        let mut remaining = String::with_capacity(description.len());
+        let mut last = 0;
+        for m in find_tokens(token_regex(), description) {
+            let token = m.as_str();
+            if is_mention_token(token) {
+                found_mentions.push(token);
+            } else {
+                found_hashtags.push(token);
+            }
+            remaining.push_str(&description[last..m.start()]);
+            remaining.push(' ');
+            last = m.end();
+        }
+        remaining.push_str(&description[last..]);
+
+        let tags = MentionsHashtags {
+            mentions: dedup_in_order(found_mentions.into_iter()),
+            hashtags: dedup_in_order(found_hashtags.into_iter()),
+        };
+        (tags, collapse_whitespace(&remaining))
+    }
+
+    /// Extracts unique `@mentions` as slices borrowed from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text; the returned set borrows from it
+    ///
+    /// # Returns
+    /// A `Result` containing a `HashSet<&str>` of unique mentions.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`], but no `String` is allocated per match
+    /// - A `HashSet` has no order; use [`parse_mentions`] when appearance order matters
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_borrowed;
+    ///
+    /// let set = parse_mentions_borrowed("@MrBeast @Khaby.Lame @MrBeast").unwrap();
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.contains("@MrBeast"));
+    /// ```
+    pub fn parse_mentions_borrowed(description: &str) -> Result<HashSet<&str>, ParseError> {
+        Ok(iter_mentions(description).collect())
+    }
+
+    /// Extracts unique `#hashtags` as slices borrowed from the input text.
+    ///
+    /// # Arguments
+    /// - `description`: The input text; the returned set borrows from it
+    ///
+    /// # Returns
+    /// A `Result` containing a `HashSet<&str>` of unique hashtags.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`], but no `String` is allocated per match
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_borrowed;
+    ///
+    /// let set = parse_hashtags_borrowed("#fyp #viral #fyp").unwrap();
+    /// assert!(set.contains("#fyp") && set.contains("#viral"));
+    /// ```
+    pub fn parse_hashtags_borrowed(description: &str) -> Result<HashSet<&str>, ParseError> {
+        Ok(iter_hashtags(description).collect())
+    }
+
+    /// Adds the `@mentions` of the input text to a caller-owned set.
+    ///
+    /// # Arguments
+    /// - `description`: The input text
+    /// - `out`: The set to add to; existing entries are kept
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`]
+    /// - Reusing one set across many texts avoids a new set per call (call `out.clear()`
+    ///   between documents to keep its capacity), and a `String` is only allocated for a
+    ///   mention not already in `out`
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(not(feature = "std"))]
+    /// # use std::collections::BTreeSet as HashSet;
+    /// # #[cfg(feature = "std")]
+    /// use std::collections::HashSet;
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_into;
+    ///
+    /// let mut seen = HashSet::new();
+    /// for text in ["@a @b", "@b @c"] {
+    ///     parse_mentions_into(text, &mut seen);
+    /// }
+    /// assert_eq!(seen.len(), 3);
+    /// ```
+    pub fn parse_mentions_into(description: &str, out: &mut HashSet<String>) {
+        extend_set(out, iter_mentions(description));
+    }
+
+    /// Adds the `#hashtags` of the input text to a caller-owned set.
+    ///
+    /// The hashtag counterpart of [`parse_mentions_into`], with the rules of [`parse_hashtags`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(not(feature = "std"))]
+    /// # use std::collections::BTreeSet as HashSet;
+    /// # #[cfg(feature = "std")]
+    /// use std::collections::HashSet;
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_into;
+    ///
+    /// let mut tags = HashSet::from(["#fyp".to_string()]);
+    /// parse_hashtags_into("#fyp #viral", &mut tags);
+    /// assert!(tags.contains("#viral") && tags.len() == 2);
+    /// ```
+    pub fn parse_hashtags_into(description: &str, out: &mut HashSet<String>) {
+        extend_set(out, iter_hashtags(description));
+    }
+
+    /// Extracts every mention and hashtag as one interleaved list, in source order.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., text being syntax-highlighted)
+    ///
+    /// # Returns
+    /// A `Vec` of `(token, start, end)` tuples, where `start` and `end` are byte offsets into
+    /// `description`.
+    ///
+    /// # Behavior
+    /// - Returns **every** occurrence (no deduplication), ordered by position
+    /// - Same matching rules as [`mentions`] and [`hashtags`]
+    /// - Unlike [`MentionsHashtags`], keeps the interleaving of the two kinds
+    /// - Offsets are bytes, for slicing `description`; see [`parse_tokens_char_spans`] for
+    ///   character offsets
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_tokens, Token};
+    ///
+    /// let tokens = parse_tokens("#fyp by @MrBeast #viral");
+    /// assert_eq!(tokens[0], (Token::Hashtag("#fyp".to_string()), 0, 4));
+    /// assert_eq!(tokens[1], (Token::Mention("@MrBeast".to_string()), 8, 16));
+    /// assert_eq!(tokens[2].0.as_str(), "#viral");
+    /// ```
+    pub fn parse_tokens(description: &str) -> Vec<(Token, usize, usize)> {
+        let mut tokens = Vec::new();
+        visit_tokens(description, |kind, text, start, end| {
+            let text = text.to_string();
+            let token = match kind {
+                TokenKind::Mention => Token::Mention(text),
+                TokenKind::Hashtag => Token::Hashtag(text),
+            };
+            tokens.push((token, start, end));
+        });
+        tokens
+    }
+
+    /// Like [`parse_tokens`], but returns named [`TokenSpan`] structs instead of tuples.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a post rendered by a web frontend)
+    ///
+    /// # Returns
+    /// One [`TokenSpan`] per occurrence, in source order, with byte offsets.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_token_spans, TokenKind};
+    ///
+    /// let spans = parse_token_spans("@x #y");
+    /// assert_eq!(spans[0].kind, TokenKind::Mention);
+    /// assert_eq!((spans[1].text.as_str(), spans[1].start, spans[1].end), ("#y", 3, 5));
+    /// ```
+    pub fn parse_token_spans(description: &str) -> Vec<TokenSpan> {
+        let mut spans = Vec::new();
+        visit_tokens(description, |kind, text, start, end| {
+            spans.push(TokenSpan {
+                kind,
+                text: text.to_string(),
+                start,
+                end,
+            });
+        });
+        spans
+    }
+
+    /// Like [`parse_tokens`], but with spans counted in characters instead of bytes.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., text highlighted by a frontend)
+    ///
+    /// # Returns
+    /// A `Vec` of `(token, start, end)` tuples, where `start` and `end` are char (Unicode
+    /// scalar value) indices into `description`.
+    ///
+    /// # Behavior
+    /// - Byte and char offsets agree only for ASCII text: in `"é #a"` the hashtag starts at
+    ///   byte 3 but char 2. Slice a Rust `&str` with byte offsets, never char offsets
+    /// - JavaScript string indices are UTF-16 code units, which match char indices only when
+    ///   there are no characters outside the Basic Multilingual Plane (most emoji count as
+    ///   two units); convert with `char::len_utf16` if that matters
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_tokens, parse_tokens_char_spans, Token};
+    ///
+    /// let text = "café #fyp";
+    /// assert_eq!(parse_tokens(text)[0].1, 6);
+    /// assert_eq!(parse_tokens_char_spans(text)[0], (Token::Hashtag("#fyp".to_string()), 5, 9));
+    /// ```
+    pub fn parse_tokens_char_spans(description: &str) -> Vec<(Token, usize, usize)> {
+        // Counts chars incrementally, since tokens arrive in order.
+        let mut byte = 0;
+        let mut char_index = 0;
+        let mut to_chars = |offset: usize| {
+            char_index += description[byte..offset].chars().count();
+            byte = offset;
+            char_index
+        };
+        parse_tokens(description)
+            .into_iter()
+            .map(|(token, start, end)| {
+                let start = to_chars(start);
+                (token, start, to_chars(end))
+            })
+            .collect()
+    }
+
+    /// Calls `visitor` for every mention and hashtag, in source order, without allocating.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a message fed into a counter or a database batch)
+    /// - `visitor`: Called with each token's kind, its text (a slice of `description`,
+    ///   including the sigil) and its start and end byte offsets
+    ///
+    /// # Behavior
+    /// - Visits **every** occurrence (no deduplication); same matching rules as [`mentions`]
+    ///   and [`hashtags`]
+    /// - The lowest-level entry point: [`parse_tokens`] is built on it
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{visit_tokens, TokenKind};
+    ///
+    /// let mut hashtag_bytes = 0;
+    /// visit_tokens("#fyp by @MrBeast #fyp", |kind, _token, start, end| {
+    ///     if kind == TokenKind::Hashtag {
+    ///         hashtag_bytes += end - start;
+    ///     }
+    /// });
+    /// assert_eq!(hashtag_bytes, 8);
+    /// ```
+    pub fn visit_tokens<'a>(
+        description: &'a str,
+        mut visitor: impl FnMut(TokenKind, &'a str, usize, usize),
+    ) {
+        for m in find_tokens(token_regex(), description) {
+            let kind = if is_mention_token(m.as_str()) {
+                TokenKind::Mention
+            } else {
+                TokenKind::Hashtag
+            };
+            visitor(kind, m.as_str(), m.start(), m.end());
+        }
+    }
+
+    /// Extracts the unique matches of a caller-supplied regex, as an escape hatch for token
+    /// rules the built-in options don't cover.
+    ///
+    /// # Arguments
+    /// - `description`: The input text
+    /// - `pattern`: Any compiled regex; each non-overlapping match is one token
+    ///
+    /// # Returns
+    /// The matched strings, deduplicated in order of first appearance like [`mentions`].
+    ///
+    /// # Behavior
+    /// - Only the dedup and ordering are applied: the e-mail check of [`mentions`] and other
+    ///   built-in filters are not, so the pattern alone decides what a token is
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_with_regex;
+    /// use regex::Regex;
+    ///
+    /// let issues = Regex::new(r"\bGH-\d+\b").unwrap();
+    /// assert_eq!(parse_with_regex("fixes GH-12, GH-7 and GH-12", &issues), vec!["GH-12", "GH-7"]);
+    /// ```
+    pub fn parse_with_regex(description: &str, pattern: &Regex) -> Vec<String> {
+        dedup_in_order(pattern.find_iter(description).map(|m| m.as_str()))
+    }
+
+    /// Extracts unique tokens introduced by an arbitrary sigil character.
+    ///
+    /// # Arguments
+    /// - `description`: The input text
+    /// - `sigil`: The marker character (e.g. `'+'` for old Google+ mentions, `'~'` for handles)
+    ///
+    /// # Returns
+    /// A `Result` containing a `Vec<String>` of unique tokens, each including the sigil.
+    ///
+    /// # Behavior
+    /// - Token bodies follow the same rules as [`mentions`] and [`hashtags`]
+    ///   (letters, digits, marks and [`DEFAULT_TOKEN_CHARS`])
+    /// - Like mentions, a sigil glued to a preceding word (e.g. `a+b`) does not start a token;
+    ///   `'#'` is the exception and behaves exactly like [`hashtags`]
+    /// - Deduplicated in order of first appearance; the sigil is escaped before use
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_with_sigil;
+    ///
+    /// let result = parse_with_sigil("+Larry +Sergey and a+b", '+').unwrap();
+    /// assert_eq!(result, vec!["+Larry", "+Sergey"]);
+    /// ```
+    pub fn parse_with_sigil(description: &str, sigil: char) -> Result<Vec<String>, ParseError> {
+        let matches = Regex::new(&token_pattern(sigil, DEFAULT_TOKEN_CHARS))?;
+        Ok(if sigil == '#' {
+            dedup_in_order(matches.find_iter(description).map(|x| x.as_str()))
+        } else {
+            dedup_in_order(find_mentions(&matches, description).map(|m| m.as_str()))
+        })
+    }
+
+    /// Parses many descriptions in parallel using `rayon`.
+    ///
+    /// # Arguments
+    /// - `descriptions`: The input texts (e.g., a nightly dump of captions)
+    /// - `mentions`: Whether to extract `@mentions`
+    /// - `hashtags`: Whether to extract `#hashtags`
+    ///
+    /// # Returns
+    /// One `MentionsHashtags` per input, in the **same order** as `descriptions`.
+    ///
+    /// # Behavior
+    /// - Each element is parsed exactly like [`parse_mentions_hashtags`]
+    /// - All threads share the same compiled regex
+    /// - Only available with the `rayon` feature
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_batch;
+    ///
+    /// let results = parse_batch(&["@a #x", "#y"], true, true);
+    /// assert_eq!(results[0].mentions, vec!["@a"]);
+    /// assert_eq!(results[1].hashtags, vec!["#y"]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn parse_batch(
+        descriptions: &[&str],
+        mentions: bool,
+        hashtags: bool,
+    ) -> Vec<MentionsHashtags> {
+        use rayon::prelude::*;
+
+        descriptions
+            .par_iter()
+            .map(|description| extract(description, mentions, hashtags))
+            .collect()
+    }
+
+    /// How [`aggregate_hashtags`] and [`aggregate_mentions`] count a token across a batch.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum AggregateMode {
+        /// Count the number of texts a token appears in (at most 1 per text).
+        #[default]
+        Texts,
+        /// Count every occurrence, including repeats within the same text.
+        Occurrences,
+    }
+
+    /// Aggregates `#hashtags` across many texts into a global frequency map.
+    ///
+    /// # Arguments
+    /// - `descriptions`: The input texts (e.g., a day's worth of captions)
+    /// - `mode`: Whether to count texts containing a hashtag or total occurrences
+    ///
+    /// # Returns
+    /// A `HashMap` from each hashtag to its aggregated count.
+    ///
+    /// # Behavior
+    /// - Each text is scanned with [`iter_hashtags`], so matching is identical to [`hashtags`]
+    /// - Counting is case-sensitive
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{aggregate_hashtags, AggregateMode};
+    ///
+    /// let texts = ["#fyp #fyp", "#fyp #viral"];
+    /// assert_eq!(aggregate_hashtags(&texts, AggregateMode::Texts)["#fyp"], 2);
+    /// assert_eq!(aggregate_hashtags(&texts, AggregateMode::Occurrences)["#fyp"], 3);
+    /// ```
+    pub fn aggregate_hashtags(
+        descriptions: &[&str],
+        mode: AggregateMode,
+    ) -> HashMap<String, usize> {
+        aggregate(descriptions, mode, iter_hashtags)
+    }
+
+    /// Aggregates `@mentions` across many texts into a global frequency map.
+    ///
+    /// # Arguments
+    /// - `descriptions`: The input texts
+    /// - `mode`: Whether to count texts containing a mention or total occurrences
+    ///
+    /// # Returns
+    /// A `HashMap` from each mention to its aggregated count.
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{aggregate_mentions, AggregateMode};
+    ///
+    /// let counts = aggregate_mentions(&["@a @a", "@a @b"], AggregateMode::Texts);
+    /// assert_eq!(counts["@a"], 2);
+    /// assert_eq!(counts["@b"], 1);
+    /// ```
+    pub fn aggregate_mentions(
+        descriptions: &[&str],
+        mode: AggregateMode,
+    ) -> HashMap<String, usize> {
+        aggregate(descriptions, mode, iter_mentions)
+    }
+
+    /// Compares the hashtags of two versions of a text, e.g. a caption before and after an edit.
+    ///
+    /// # Arguments
+    /// - `before`: The original text
+    /// - `after`: The edited text
+    ///
+    /// # Returns
+    /// `(added, removed)`: the hashtags only in `after`, in their order there, and the hashtags
+    /// only in `before`, in their order there. Both are unique.
+    ///
+    /// # Behavior
+    /// - Same matching and case rules as [`parse_hashtags`]: `#Fyp` and `#fyp` differ
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::hashtag_diff;
+    ///
+    /// let (added, removed) = hashtag_diff("#fyp #old", "#fyp #new");
+    /// assert_eq!(added, vec!["#new"]);
+    /// assert_eq!(removed, vec!["#old"]);
+    /// ```
+    pub fn hashtag_diff(before: &str, after: &str) -> (Vec<String>, Vec<String>) {
+        let old: HashSet<&str> = iter_hashtags(before).collect();
+        let new: HashSet<&str> = iter_hashtags(after).collect();
+        let added = dedup_in_order(iter_hashtags(after).filter(|tag| !old.contains(tag)));
+        let removed = dedup_in_order(iter_hashtags(before).filter(|tag| !new.contains(tag)));
+        (added, removed)
+    }
+
+    /// Maps each hashtag to the lines it appears on, for a cross-reference index.
+    ///
+    /// # Arguments
+    /// - `lines`: The input lines (e.g., a log split on newlines)
+    ///
+    /// # Returns
+    /// A `HashMap` from each hashtag to the 0-based indices into `lines` where it occurs,
+    /// in increasing order.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_hashtags`]; comparison is exact
+    /// - A line is listed once per hashtag, however often the tag repeats on it
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_hashtags_by_line;
+    ///
+    /// let index = parse_hashtags_by_line(&["#fyp #fyp", "nothing", "#viral #fyp"]);
+    /// assert_eq!(index["#fyp"], vec![0, 2]);
+    /// assert_eq!(index["#viral"], vec![2]);
+    /// ```
+    pub fn parse_hashtags_by_line(lines: &[&str]) -> HashMap<String, Vec<usize>> {
+        let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+        for (line_index, line) in lines.iter().enumerate() {
+            for hashtag in iter_hashtags(line) {
+                match index.get_mut(hashtag) {
+                    Some(found) if found.last() == Some(&line_index) => {}
+                    Some(found) => found.push(line_index),
+                    None => {
+                        index.insert(hashtag.to_string(), vec![line_index]);
+                    }
+                }
+            }
+        }
+        index
+    }
+
+    /// A social platform whose username rules [`parse_mentions_for`] enforces.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub enum Platform {
+        /// X / Twitter: 1–15 ASCII letters, digits or `_`; not purely numeric; may not contain
+        /// `twitter` or `admin`.
+        Twitter,
+        /// Instagram: 1–30 ASCII letters, digits, `_` or `.`; no leading, trailing or
+        /// consecutive dots.
+        Instagram,
+        /// TikTok: 2–24 ASCII letters, digits, `_` or `.`; may not end with a dot.
+        TikTok,
+        /// No platform rules; behaves like [`mentions`].
+        #[default]
+        Generic,
+    }
+
+    impl Platform {
+        /// Whether `handle` (without the `@`) is a valid username on this platform.
+        ///
+        /// # Examples
+        /// ```
+        /// use mentions_hashtags::mentions_hashtags::Platform;
+        ///
+        /// assert!(Platform::Twitter.is_valid_handle("jack"));
+        /// assert!(!Platform::Twitter.is_valid_handle("12345"));
+        /// assert!(Platform::Instagram.is_valid_handle("foo.bar"));
+        /// assert!(!Platform::Instagram.is_valid_handle("foo..bar"));
+        /// ```
+        pub fn is_valid_handle(self, handle: &str) -> bool {
+            let len = handle.chars().count();
+            match self {
+                Platform::Twitter => {
+                    let lower = handle.to_lowercase();
+                    (1..=15).contains(&len)
+                        && handle
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                        && !handle.chars().all(|c| c.is_ascii_digit())
+                        && !lower.contains("twitter")
+                        && !lower.contains("admin")
+                }
+                Platform::Instagram => {
+                    (1..=30).contains(&len)
+                        && handle
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+                        && !handle.starts_with('.')
+                        && !handle.ends_with('.')
+                        && !handle.contains("..")
+                }
+                Platform::TikTok => {
+                    (2..=24).contains(&len)
+                        && handle
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+                        && !handle.ends_with('.')
+                }
+                Platform::Generic => {
+                    let mention = format!("@{handle}");
+                    mention_regex()
+                        .find(&mention)
+                        .is_some_and(|m| m.start() == 0 && m.end() == mention.len())
+                }
+            }
+        }
+
+        /// The regex producing candidate mentions for this platform.
+        fn mention_regex(self) -> &'static Regex {
+            match self {
+                Platform::Twitter => cached_regex!(TWITTER_MENTION_PATTERN),
+                Platform::Instagram => cached_regex!(INSTAGRAM_MENTION_PATTERN),
+                Platform::TikTok => cached_regex!(TIKTOK_MENTION_PATTERN),
+                Platform::Generic => mention_regex(),
+            }
+        }
+    }
+
+    /// Extracts unique `@mentions` that are valid usernames on the given platform.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a tweet or an Instagram caption)
+    /// - `platform`: Whose username rules to enforce
+    ///
+    /// # Returns
+    /// A `Vec<String>` of unique, valid mentions (with the `@`).
+    ///
+    /// # Behavior
+    /// - Candidates are matched with the platform's character set (so on Twitter
+    ///   `@user.name` yields `@user`, as Twitter itself would link it)
+    /// - A candidate cut short by a character outside the platform's set that would still
+    ///   continue the word (a letter, number or mark, as in `@josé`) is dropped, not truncated
+    ///   to `@jos`
+    /// - Trailing dots are trimmed first, so a sentence-final period (`"by @natgeo."`) still
+    ///   yields `@natgeo`
+    /// - Each candidate is then checked with [`Platform::is_valid_handle`]; invalid ones (too
+    ///   long, purely numeric, reserved words, interior `..`, ...) are dropped, not truncated
+    /// - [`Platform::Generic`] returns exactly what [`mentions`] returns
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_mentions_for, Platform};
+    ///
+    /// let text = "@jack @123456 @this_name_is_way_too_long @user.name";
+    /// assert_eq!(parse_mentions_for(text, Platform::Twitter), vec!["@jack", "@user"]);
+    /// ```
+    pub fn parse_mentions_for(description: &str, platform: Platform) -> Vec<String> {
+        dedup_in_order(
+            find_mentions(platform.mention_regex(), description)
+                .filter(|m| {
+                    !description[m.end()..]
+                        .chars()
+                        .next()
+                        .is_some_and(is_word_char)
+                })
+                .map(|m| match platform {
+                    Platform::Generic => m.as_str(),
+                    _ => m.as_str().trim_end_matches('.'),
+                })
+                .filter(|m| platform.is_valid_handle(without_sigil(m))),
+        )
+    }
+
+    /// Streams mentions and/or hashtags out of a reader, line by line.
+    ///
+    /// # Arguments
+    /// - `reader`: Any `BufRead` source (e.g. a `BufReader<File>` over a large transcript)
+    /// - `mentions`: Whether to report `@mentions`
+    /// - `hashtags`: Whether to report `#hashtags`
+    /// - `on_token`: Called once for each **new** token, in order of first appearance
+    ///
+    /// # Returns
+    /// `Ok(())` once the reader is exhausted, or the first I/O error. Invalid UTF-8 is reported
+    /// as an `InvalidData` error.
+    ///
+    /// # Behavior
+    /// - Deduplicates globally across the whole stream, like [`parse_mentions_hashtags`]
+    /// - Only one line is held in memory at a time (plus the set of tokens seen so far); tokens
+    ///   never span lines, so nothing is lost at line boundaries
+    /// - Only available with the `std` feature
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_reader, Token};
+    ///
+    /// let transcript = "@host welcome #ep1\n@guest hi @host\n#ep1 #rust\n";
+    /// let mut tokens = Vec::new();
+    /// parse_reader(transcript.as_bytes(), true, true, |token| tokens.push(token)).unwrap();
+    /// assert_eq!(
+    ///     tokens,
+    ///     vec![
+    ///         Token::Mention("@host".to_string()),
+    ///         Token::Hashtag("#ep1".to_string()),
+    ///         Token::Mention("@guest".to_string()),
+    ///         Token::Hashtag("#rust".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn parse_reader<R: std::io::BufRead>(
+        mut reader: R,
+        mentions: bool,
+        hashtags: bool,
+        mut on_token: impl FnMut(Token),
+    ) -> std::io::Result<()> {
+        let mut seen = HashSet::new();
+        let mut line = String::new();
+        while reader.read_line(&mut line)? != 0 {
+            for m in find_tokens(token_regex(), &line) {
+                let token = m.as_str();
+                let is_mention = is_mention_token(token);
+                if (is_mention && !mentions) || (!is_mention && !hashtags) {
+                    continue;
+                }
+                if !seen.contains(token) {
+                    let token = token.to_string();
+                    seen.insert(token.clone());
+                    on_token(if is_mention {
+                        Token::Mention(token)
+                    } else {
+                        Token::Hashtag(token)
+                    });
+                }
+            }
+            line.clear();
+        }
+        Ok(())
+    }
+
+    /// Reads a whole file and extracts its mentions and/or hashtags.
+    ///
+    /// # Arguments
+    /// - `path`: The file to read (e.g., a dump of captions, one per line)
+    /// - `mentions`: Whether to extract mentions
+    /// - `hashtags`: Whether to extract hashtags
+    ///
+    /// # Returns
+    /// The same result as [`parse_mentions_hashtags`] on the file's contents, or the I/O
+    /// error from reading it.
+    ///
+    /// # Behavior
+    /// - A file that isn't valid UTF-8 fails with [`std::io::ErrorKind::InvalidData`], and
+    ///   the message names the path and the byte offset of the first invalid sequence
+    /// - The whole file is loaded into memory; use [`parse_reader`] for large inputs
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use mentions_hashtags::mentions_hashtags::parse_file;
+    ///
+    /// let result = parse_file("captions.txt", true, true)?;
+    /// println!("{} hashtags", result.hashtags.len());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn parse_file<P: AsRef<std::path::Path>>(
+        path: P,
+        mentions: bool,
+        hashtags: bool,
+    ) -> std::io::Result<MentionsHashtags> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        let description = core::str::from_utf8(&bytes).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{} is not valid UTF-8 (invalid byte at offset {})",
+                    path.display(),
+                    err.valid_up_to()
+                ),
+            )
+        })?;
+        Ok(extract(description, mentions, hashtags))
+    }
+
+    /// Finds every mention and hashtag with its line and column.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a markdown document being linted)
+    ///
+    /// # Returns
+    /// A `Vec<TokenLocation>` with one entry per occurrence, in source order.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_tokens`]; duplicates are kept
+    /// - Lines are split on `\n` (a preceding `\r` just counts as a character), and both line
+    ///   and column are 1-based
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_with_locations;
+    ///
+    /// let locations = parse_with_locations("# Notes\n\nThanks @foo for #rust");
+    /// assert_eq!(locations[0].text, "@foo");
+    /// assert_eq!((locations[0].line, locations[0].column), (3, 8));
+    /// assert_eq!((locations[1].line, locations[1].column), (3, 17));
+    /// ```
+    pub fn parse_with_locations(description: &str) -> Vec<TokenLocation> {
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut scanned = 0;
+        find_tokens(token_regex(), description)
+            .map(|m| {
+                for (offset, _) in description[scanned..m.start()].match_indices('\n') {
+                    line += 1;
+                    line_start = scanned + offset + 1;
+                }
+                scanned = m.start();
+                TokenLocation {
+                    text: m.as_str().to_string(),
+                    line,
+                    column: description[line_start..m.start()].chars().count() + 1,
+                    byte_start: m.start(),
+                    byte_end: m.end(),
+                }
+            })
+            .collect()
+    }
+
+    /// Extracts unique mentions and hashtags plus their counts in a single scan.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a caption shown on a dashboard)
+    ///
+    /// # Returns
+    /// A [`ParseStats`] with the unique lists and the per-token counts.
+    ///
+    /// # Behavior
+    /// - Lists match [`parse_mentions_hashtags`] with both kinds enabled; counts match
+    ///   [`count_mentions`] / [`count_hashtags`]
+    /// - The text is scanned once, instead of once per function
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_stats;
+    ///
+    /// let stats = parse_stats("#fyp @a #viral #fyp");
+    /// assert_eq!(stats.hashtags, vec!["#fyp", "#viral"]);
+    /// assert_eq!(stats.hashtag_counts["#fyp"], 2);
+    /// assert_eq!(stats.mention_counts["@a"], 1);
+    /// ```
+    pub fn parse_stats(description: &str) -> ParseStats {
+        let mut stats = ParseStats::default();
+        for m in find_tokens(token_regex(), description) {
+            let token = m.as_str();
+            let (list, counts) = if is_mention_token(token) {
+                (&mut stats.mentions, &mut stats.mention_counts)
+            } else {
+                (&mut stats.hashtags, &mut stats.hashtag_counts)
+            };
+            let count = counts.entry(token.to_string()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                list.push(token.to_string());
+            }
+        }
+        stats
+    }
+
+    /// Extracts unique mentions, keeping the `@instance` part of fediverse handles.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a Mastodon post)
+    ///
+    /// # Returns
+    /// A `Vec<String>` of unique mentions, such as `@user` or `@user@instance.social`.
+    ///
+    /// # Behavior
+    /// - Plain `@user` mentions are matched exactly as by [`mentions`]
+    /// - A directly following `@domain` with at least one dot is kept as part of the mention;
+    ///   a trailing sentence dot is not
+    /// - An e-mail such as `a@b.com` is still skipped, because its `@` is glued to a word
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_fediverse_mentions;
+    ///
+    /// let text = "cc @Gargron@mastodon.social and @alice, mail bob@example.com";
+    /// assert_eq!(
+    ///     parse_fediverse_mentions(text),
+    ///     vec!["@Gargron@mastodon.social", "@alice"]
+    /// );
+    /// ```
+    pub fn parse_fediverse_mentions(description: &str) -> Vec<String> {
+        dedup_in_order(
+            find_mentions(cached_regex!(FEDIVERSE_MENTION_PATTERN), description)
+                .map(|m| m.as_str()),
+        )
+    }
+
+    /// Returns the key this crate uses to compare tokens case-insensitively.
+    ///
+    /// # Arguments
+    /// - `token`: A mention or hashtag, including its sigil (e.g. `#Music`)
+    ///
+    /// # Returns
+    /// The token lowercased with Unicode-aware `to_lowercase`, with a full-width sigil (`＠`,
+    /// `＃`) replaced by its ASCII form.
+    ///
+    /// # Behavior
+    /// - Used internally by [`parse_mentions_dedup_ci`], [`parse_hashtags_dedup_ci`] and the
+    ///   [`Extractor`] case options, so deduplicating with it downstream gives the same result
+    /// - Text without a full-width sigil is simply lowercased
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::canonical_key;
+    ///
+    /// assert_eq!(canonical_key("#Music"), "#music");
+    /// assert_eq!(canonical_key("＠Дмитрий"), "@дмитрий");
+    /// ```
+    pub fn canonical_key(token: &str) -> String {
+        let mut key = String::with_capacity(token.len());
+        let rest = match token.chars().next() {
+            Some(FULLWIDTH_AT) => {
+                key.push('@');
+                without_sigil(token)
+            }
+            Some(FULLWIDTH_HASH) => {
+                key.push('#');
+                without_sigil(token)
+            }
+            _ => token,
+        };
+        key.push_str(&rest.to_lowercase());
+        key
+    }
+
+    /// Finds every `@mention` together with a snippet of the text around it.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a comment flagged for moderation)
+    /// - `window`: How many characters to include on each side of the mention
+    ///
+    /// # Returns
+    /// A `Vec<(String, String)>` of `(mention, snippet)` pairs, one per occurrence, in source
+    /// order. The snippet contains the mention itself.
+    ///
+    /// # Behavior
+    /// - Same matching rules as [`parse_mentions`]; duplicates are kept, each with its own
+    ///   context
+    /// - `window` counts characters (Unicode scalar values), so multi-byte characters are never
+    ///   split; the snippet is clamped to the start and end of the text
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_with_context;
+    ///
+    /// let found = parse_mentions_with_context("I just posted @spammer check it out", 7);
+    /// assert_eq!(found, vec![("@spammer".to_string(), "posted @spammer check ".to_string())]);
+    /// ```
+    pub fn parse_mentions_with_context(description: &str, window: usize) -> Vec<(String, String)> {
+        find_mentions(mention_regex(), description)
+            .map(|m| {
+                let start = description[..m.start()]
+                    .char_indices()
+                    .rev()
+                    .take(window)
+                    .last()
+                    .map_or(m.start(), |(i, _)| i);
+                let end = description[m.end()..]
+                    .char_indices()
+                    .nth(window)
+                    .map_or(description.len(), |(i, _)| m.end() + i);
+                (m.as_str().to_string(), description[start..end].to_string())
+            })
+            .collect()
+    }
+
+    /// Finds the `@mentions` overlapping a byte range, scanning only around that range.
+    ///
+    /// # Arguments
+    /// - `description`: The full document
+    /// - `range`: The byte range to look at (e.g., the region just edited)
+    ///
+    /// # Returns
+    /// A `Vec` of `(mention, start, end)` tuples, one per occurrence, with byte offsets into
+    /// the whole `description`.
+    ///
+    /// # Behavior
+    /// - A mention is included if any of its bytes fall inside `range`, so a mention cut by
+    ///   either end of the range is returned whole; an empty range selects a mention only if
+    ///   it lies strictly around that position
+    /// - Finds what a scan of the whole text would find there (e-mail check included), but
+    ///   only the range and the non-whitespace text around it are scanned
+    /// - Out-of-bounds ends are clamped and ends inside a character are widened to it
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_mentions_in_range;
+    ///
+    /// let doc = "@alice wrote\nhi @bob and @carol";
+    /// assert_eq!(parse_mentions_in_range(doc, 17..19), vec![("@bob".to_string(), 16, 20)]);
+    /// assert_eq!(parse_mentions_in_range(doc, 0..doc.len()).len(), 3);
+    /// ```
+    pub fn parse_mentions_in_range(
+        description: &str,
+        range: Range<usize>,
+    ) -> Vec<(String, usize, usize)> {
+        let start = floor_char_boundary(description, range.start);
+        let mut end = range.end.clamp(start, description.len());
+        while !description.is_char_boundary(end) {
+            end += 1;
+        }
+        // Tokens never contain whitespace, so widening to it catches every overlapping one.
+        let scan_start = description[..start]
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| {
+                i + description[i..].chars().next().map_or(0, char::len_utf8)
+            });
+        let scan_end = description[end..]
+            .find(char::is_whitespace)
+            .map_or(description.len(), |i| end + i);
+        let overlaps = |s: usize, e: usize| {
+            if start < end {
+                s < end && e > start
+            } else {
+                s < start && e > start
+            }
+        };
+        mention_regex()
+            .find_iter(&description[scan_start..scan_end])
+            .map(|m| (m.start() + scan_start, m.end() + scan_start))
+            .filter(|&(s, e)| overlaps(s, e) && is_mention_start(description, s))
+            .map(|(s, e)| (description[s..e].to_string(), s, e))
+            .collect()
+    }
+
+    /// Splits the unique `@mentions` into brand mentions and all others.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a sponsored post)
+    /// - `brands`: The brand handles, with or without the `@`, in any case
+    ///
+    /// # Returns
+    /// `(brand, other)`: the mentions of [`mentions`] that are in `brands`, and the rest, both
+    /// in order of first appearance and as written in the text.
+    ///
+    /// # Behavior
+    /// - Comparison ignores case and a full-width `＠`, like [`canonical_key`]
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(not(feature = "std"))]
+    /// # use std::collections::BTreeSet as HashSet;
+    /// # #[cfg(feature = "std")]
+    /// use std::collections::HashSet;
+    /// use mentions_hashtags::mentions_hashtags::classify_mentions;
+    ///
+    /// let brands = HashSet::from(["@Gucci".to_string(), "nike".to_string()]);
+    /// let (brand, other) = classify_mentions("@gucci x @Nike by @charlidamelio", &brands);
+    /// assert_eq!(brand, vec!["@gucci", "@Nike"]);
+    /// assert_eq!(other, vec!["@charlidamelio"]);
+    /// ```
+    pub fn classify_mentions(
+        description: &str,
+        brands: &HashSet<String>,
+    ) -> (Vec<String>, Vec<String>) {
+        let brands: HashSet<String> = brands.iter().map(|brand| canonical_key(brand)).collect();
+        mentions(description)
+            .into_iter()
+            .partition(|mention| in_token_list(&brands, mention))
+    }
+
+    /// How a mention relates to the post, from the keyword right before it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum Relation {
+        /// `via @user`: the post credits the user as its source.
+        Via,
+        /// `cc: @user`: the user is copied in.
+        Cc,
+        /// `RT @user`: the post is a retweet of the user.
+        Retweet,
+    }
+
+    /// Keywords recognized by [`parse_mentions_with_relations`], compared ignoring ASCII case.
+    const RELATION_KEYWORDS: [(&str, Relation); 3] = [
+        ("via", Relation::Via),
+        ("cc:", Relation::Cc),
+        ("rt", Relation::Retweet),
+    ];
+
+    /// Finds the unique `@mentions`, labelling those introduced by `via`, `cc:` or `rt`.
+    ///
+    /// # Arguments
+    /// - `description`: The input text (e.g., a post being analyzed for attribution)
+    ///
+    /// # Returns
+    /// A `Vec<(String, Option<Relation>)>` with the mentions of [`mentions`], in the same order.
+    ///
+    /// # Behavior
+    /// - A keyword counts when only whitespace separates it from the mention and it is a whole
+    ///   word (`RT @a` and `cc:@a` count, `covia @a` doesn't); case is ignored
+    /// - Only the mention right after the keyword is labelled: in `cc: @a @b`, `@b` gets `None`
+    /// - A repeated mention takes the relation of its first labelled occurrence
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::{parse_mentions_with_relations, Relation};
+    ///
+    /// let found = parse_mentions_with_relations("Great thread by @a (via @b) cc: @c");
+    /// assert_eq!(
+    ///     found,
+    ///     vec![
+    ///         ("@a".to_string(), None),
+    ///         ("@b".to_string(), Some(Relation::Via)),
+    ///         ("@c".to_string(), Some(Relation::Cc)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn parse_mentions_with_relations(description: &str) -> Vec<(String, Option<Relation>)> {
+        let mut found: Vec<(String, Option<Relation>)> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for m in find_mentions(mention_regex(), description) {
+            let relation = relation_before(&description[..m.start()]);
+            match index.get(m.as_str()) {
+                Some(&i) => {
+                    found[i].1 = found[i].1.or(relation);
+                }
+                None => {
+                    index.insert(m.as_str(), found.len());
+                    found.push((m.as_str().to_string(), relation));
+                }
+            }
+        }
+        found
+    }
+
+    /// Parses mentions and hashtags on tokio's blocking thread pool.
+    ///
+    /// # Arguments
+    /// - `description`: The input text, owned so it can move to the blocking thread
+    ///
+    /// # Returns
+    /// A future resolving to the same `MentionsHashtags` as
+    /// `parse_mentions_hashtags(&description, true, true)`.
+    ///
+    /// # Behavior
+    /// - Wraps the synchronous parse in `tokio::task::spawn_blocking`, so long captions don't
+    ///   stall the async worker threads
+    /// - Must be awaited inside a tokio runtime
+    /// - Only available with the `tokio` feature
+    ///
+    /// # Panics
+    /// - If the parse itself panics, that panic is resumed on the awaiting task with its
+    ///   original payload (the parser is not expected to panic)
+    /// - If the blocking task is cancelled because the runtime is shutting down
+    ///
+    /// # Examples
+    /// ```
+    /// use mentions_hashtags::mentions_hashtags::parse_spawn_blocking;
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let result = runtime.block_on(parse_spawn_blocking("@a #b".to_string()));
+    /// assert_eq!(result.mentions, vec!["@a"]);
+    /// assert_eq!(result.hashtags, vec!["#b"]);
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn parse_spawn_blocking(description: String) -> MentionsHashtags {
+        match tokio::task::spawn_blocking(move || extract(&description, true, true)).await {
+            Ok(result) => result,
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            Err(err) => panic!("parse task did not complete: {err}"),
+        }
+    }
+
+    /// Removes trailing `.` and `-` from a token, returning `None` if only the sigil remains.
+    fn trim_trailing_punctuation(token: &str) -> Option<&str> {
+        let trimmed = token.trim_end_matches(['.', '-']);
+        if without_sigil(trimmed).is_empty() {
+            None
+        } else {
+            Some(trimmed)
+        }
+    }
+
+    /// Returns the token body, i.e. everything after its leading sigil character.
+    fn without_sigil(token: &str) -> &str {
+        let mut chars = token.chars();
+        chars.next();
+        chars.as_str()
+    }
+
+    /// Returns the shared, compiled [`MENTION_PATTERN`] regex, compiling it on first use.
+    ///
+    /// Matches are raw: unlike [`parse_mentions`], e-mail addresses such as `me@mail.com` are
+    /// not skipped.
+    pub fn mention_regex() -> &'static Regex {
+        cached_regex!(MENTION_PATTERN)
+    }
+
+    /// Returns the shared, compiled [`HASHTAG_PATTERN`] regex, compiling it on first use.
+    pub fn hashtag_regex() -> &'static Regex {
+        cached_regex!(HASHTAG_PATTERN)
+    }
+
+    /// Returns the shared URL regex, compiling it on first use.
+    fn url_regex() -> &'static Regex {
+        cached_regex!(URL_PATTERN)
+    }
+
+    /// Returns the shared e-mail regex, compiling it on first use.
+    fn email_regex() -> &'static Regex {
+        cached_regex!(EMAIL_PATTERN)
+    }
+
+    /// Returns the shared `$cashtag` regex, compiling it on first use.
+    fn cashtag_regex() -> &'static Regex {
+        cached_regex!(CASHTAG_PATTERN)
+    }
+
+    /// Returns the shared combined `@mention` / `#hashtag` regex, compiling it on first use.
+    fn token_regex() -> &'static Regex {
+        cached_regex!(TOKEN_PATTERN)
+    }
+
+    /// Builds a token pattern: `sigil` followed by Unicode letters, numbers, marks, or any char in
+    /// `allowed`.
+    ///
+    /// Both the sigil and the allowed set are escaped, so caller input is always taken literally.
+    fn token_pattern(sigil: char, allowed: &str) -> String {
+        let sigil = match sigil {
+            '@' => format!("[@{FULLWIDTH_AT}]"),
+            '#' => format!("[#{FULLWIDTH_HASH}]"),
+            other => regex::escape(other.encode_utf8(&mut [0; 4])),
+        };
+        // Like the built-in patterns, a token may not start with `-` or `.`.
+        let leading: String = allowed
+            .chars()
+            .filter(|c| !matches!(c, '-' | '.'))
+            .collect();
+        format!(
+            r"{}[\p{{L}}\p{{N}}{}][\p{{L}}\p{{N}}\p{{M}}{}]*",
+            sigil,
+            regex::escape(&leading),
+            regex::escape(allowed)
+        )
+    }
+
+    /// Whether a matched token is a mention, i.e. starts with `@` or its full-width form.
+    fn is_mention_token(token: &str) -> bool {
+        token.starts_with(['@', FULLWIDTH_AT])
+    }
+
+    /// Collects tokens into a `Vec`, keeping only the first occurrence of each.
+    ///
+    /// A `HashSet` tracks what has been seen while the `Vec` preserves insertion order, so the
+    /// output is deterministic and follows the source text.
+    fn dedup_in_order<'a>(tokens: impl Iterator<Item = &'a str>) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut unique = Vec::new();
+        for token in tokens {
+            if seen.insert(token) {
+                unique.push(token.to_string());
+            }
+        }
+        unique
+    }
+
+    /// Like [`dedup_in_order`], but two tokens are duplicates when `key` maps them to the same value.
+    ///
+    /// The first-seen token is returned unchanged; `key` only drives the uniqueness check.
+    fn dedup_in_order_by_key<'a>(
+        tokens: impl Iterator<Item = &'a str>,
+        key: impl Fn(&str) -> String,
+    ) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut unique = Vec::new();
+        for token in tokens {
+            if seen.insert(key(token)) {
+                unique.push(token.to_string());
+            }
+        }
+        unique
+    }
+
+    /// Finds `@mention` matches of `regex`, skipping any `@` that is glued to a preceding word
+    /// (e.g. the `@` in `john@example.com`).
+    fn find_mentions<'a>(
+        regex: &'a Regex,
+        description: &'a str,
+    ) -> impl Iterator<Item = regex::Match<'a>> + 'a {
+        regex
+            .find_iter(description)
+            .filter(move |m| is_mention_start(description, m.start()))
+    }
+
+    /// Finds matches of a combined `@`/`#` `regex`, dropping mentions rejected by
+    /// [`is_mention_start`].
+    fn find_tokens<'a>(
+        regex: &'a Regex,
+        description: &'a str,
+    ) -> impl Iterator<Item = regex::Match<'a>> + 'a {
+        regex.find_iter(description).filter(move |m| {
+            !is_mention_token(m.as_str()) || is_mention_start(description, m.start())
+        })
+    }
+
+    /// Joins the whitespace-separated words of `text` with single spaces.
+    fn collapse_whitespace(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Whether a mention may start at byte offset `start`: at the start of the text, or after a
+    /// character that is not a letter, digit or `_`.
+    fn is_mention_start(description: &str, start: usize) -> bool {
+        description[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+    }
+
+    /// Maps each token to the number of times it occurs.
+    fn count_tokens<'a>(tokens: impl Iterator<Item = &'a str>) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for token in tokens {
+            *counts.entry(token.to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Shared body of the `aggregate_*` functions; `tokens_of` yields every match in a text.
+    fn aggregate<'a, I>(
+        descriptions: &[&'a str],
+        mode: AggregateMode,
+        tokens_of: impl Fn(&'a str) -> I,
+    ) -> HashMap<String, usize>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut counts = HashMap::new();
+        for description in descriptions {
+            let mut seen = HashSet::new();
+            for token in tokens_of(description) {
+                if mode == AggregateMode::Occurrences || seen.insert(token) {
+                    *counts.entry(token.to_string()).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Applies a [`DotPolicy`] to a mention, returning `None` if it is rejected or nothing but
+    /// the sigil is left.
+    fn apply_dot_policy(token: &str, policy: DotPolicy) -> Option<&str> {
+        let bad_dots = token.contains("..") || token.ends_with('.');
+        match policy {
+            DotPolicy::Allow => Some(token),
+            DotPolicy::Reject if bad_dots => None,
+            DotPolicy::Reject => Some(token),
+            DotPolicy::Truncate => {
+                let end = token.find("..").unwrap_or(token.len());
+                let truncated = token[..end].trim_end_matches('.');
+                (!without_sigil(truncated).is_empty()).then_some(truncated)
+            }
+        }
+    }
+
+    /// Removes every token whose body is a strict, case-insensitive prefix of another's.
+    fn drop_extended_prefixes(tokens: &mut Vec<Cow<'_, str>>) {
+        let keys: Vec<String> = tokens.iter().map(|token| canonical_key(token)).collect();
+        let mut keep = keys.iter().map(|key| {
+            let body = without_sigil(key);
+            !keys.iter().any(|other| {
+                let other = without_sigil(other);
+                other.len() > body.len() && other.starts_with(body)
+            })
+        });
+        tokens.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    /// Returns the first token whose char count `beats` that of every earlier one.
+    fn extreme_by_len<'a>(
+        tokens: impl Iterator<Item = &'a str>,
+        beats: impl Fn(usize, usize) -> bool,
+    ) -> Option<&'a str> {
+        let mut best: Option<(&str, usize)> = None;
+        for token in tokens {
+            let len = token.chars().count();
+            if best.is_none_or(|(_, best_len)| beats(len, best_len)) {
+                best = Some((token, len));
+            }
+        }
+        best.map(|(token, _)| token)
+    }
+
+    /// Whether `body` contains at least `min` distinct characters, stopping once it does.
+    fn has_distinct_chars(body: &str, min: usize) -> bool {
+        let mut seen = HashSet::new();
+        min == 0 || body.chars().any(|c| seen.insert(c) && seen.len() >= min)
+    }
+
+    /// Sorts tokens case-insensitively, breaking ties by the original casing.
+    fn sort_alphabetically<T: AsRef<str> + Clone + Ord>(tokens: &mut [T]) {
+        tokens.sort_by_cached_key(|token| (token.as_ref().to_lowercase(), token.clone()));
+    }
+
+    /// Whether `token` matches a lowercased deny/allow list entry, with or without its sigil.
+    fn in_token_list(list: &HashSet<String>, token: &str) -> bool {
+        if list.is_empty() {
+            return false;
+        }
+        let token = canonical_key(token);
+        list.contains(without_sigil(&token)) || list.contains(&token)
+    }
+
+    /// Strips `chars` from both ends of a token's body, keeping the sigil.
+    ///
+    /// Borrows when only the end changes; returns `None` if nothing but the sigil is left.
+    fn trim_body<'a>(token: &'a str, chars: &[char]) -> Option<Cow<'a, str>> {
+        let body = without_sigil(token);
+        let sigil = &token[..token.len() - body.len()];
+        let trimmed = body.trim_matches(chars);
+        if trimmed.is_empty() {
+            None
+        } else if body.starts_with(trimmed) {
+            Some(Cow::Borrowed(&token[..sigil.len() + trimmed.len()]))
+        } else {
+            Some(Cow::Owned(format!("{sigil}{trimmed}")))
+        }
+    }
+
+    /// Shortens every run of more than `max` identical characters to `max`, leaving the token
+    /// untouched when there is none.
+    fn collapse_runs(token: Cow<'_, str>, max: usize) -> Cow<'_, str> {
+        let mut run = 0;
+        let mut prev = None;
+        let has_long_run = token.chars().any(|c| {
+            run = if prev == Some(c) { run + 1 } else { 1 };
+            prev = Some(c);
+            run > max
+        });
+        if !has_long_run {
+            return token;
+        }
+
+        let mut collapsed = String::with_capacity(token.len());
+        let mut run = 0;
+        let mut prev = None;
+        for c in token.chars() {
+            run = if prev == Some(c) { run + 1 } else { 1 };
+            prev = Some(c);
+            if run <= max {
+                collapsed.push(c);
+            }
+        }
+        Cow::Owned(collapsed)
+    }
+
+    /// Returns the visible text of an HTML fragment for [`ExtractorBuilder::input_is_html`],
+    /// borrowing when there is no markup.
+    fn html_to_text(html: &str) -> Cow<'_, str> {
+        if !html.contains(['<', '&']) {
+            return Cow::Borrowed(html);
+        }
+
+        let mut text = String::with_capacity(html.len());
+        let mut rest = html;
+        while let Some(i) = rest.find(['<', '&']) {
+            text.push_str(&rest[..i]);
+            rest = &rest[i..];
+            let starts_tag = rest.starts_with('<')
+                && rest[1..]
+                    .starts_with(|c: char| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+            if starts_tag {
+                rest = skip_tag(rest);
+                text.push(' ');
+            } else if let Some((c, len)) = decode_entity(rest) {
+                text.push(c);
+                rest = &rest[len..];
+            } else {
+                // A lone `<` or `&` is plain text.
+                text.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+        text.push_str(rest);
+        Cow::Owned(text)
+    }
+
+    /// Skips the tag or comment at the start of `html`, returning what follows it.
+    ///
+    /// `>` inside quoted attribute values doesn't end the tag; an unterminated tag runs to
+    /// the end of the input.
+    fn skip_tag(html: &str) -> &str {
+        if let Some(comment) = html.strip_prefix("<!--") {
+            return comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        }
+        let mut quote = None;
+        for (i, c) in html.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c == '>' => return &html[i + 1..],
+                None => {}
+            }
+        }
+        ""
+    }
+
+    /// Decodes the character reference (`&amp;`, `&#39;`, `&#x1F600;`) at the start of `html`,
+    /// returning the character and the reference's length in bytes.
+    fn decode_entity(html: &str) -> Option<(char, usize)> {
+        let end = html.bytes().take(12).position(|b| b == b';')?;
+        let c = match &html[1..end] {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{A0}',
+            name => {
+                let number = name.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)?
+            }
+        };
+        Some((c, end + 1))
+    }
+
+    /// Invisible characters removed by [`ExtractorBuilder::strip_invisible`].
+    const INVISIBLE_CHARS: [char; 5] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}', '\u{AD}'];
+
+    /// Removes [`INVISIBLE_CHARS`] from `text`, borrowing when there are none.
+    fn remove_invisible(text: &str) -> Cow<'_, str> {
+        if text.contains(INVISIBLE_CHARS) {
+            Cow::Owned(text.replace(INVISIBLE_CHARS, ""))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// Appends the tokens of `incoming` whose `key` is not yet in `tokens`.
+    fn merge_tokens(tokens: &mut Vec<String>, incoming: Vec<String>, key: impl Fn(&str) -> String) {
+        let mut seen: HashSet<String> = tokens.iter().map(|t| key(t)).collect();
+        for token in incoming {
+            if seen.insert(key(&token)) {
+                tokens.push(token);
+            }
+        }
+    }
+
+    /// Whether byte offset `start` is at the start of the text or follows whitespace, an
+    /// opening bracket or an emoji, as required by [`ExtractorBuilder::require_boundary`].
+    fn is_boundary_before(description: &str, start: usize) -> bool {
+        description[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || matches!(c, '(' | '[' | '{') || is_emoji(c))
+    }
+
+    /// Whether `c` is a letter, number or mark, per [`WORD_CHAR_PATTERN`].
+    fn is_word_char(c: char) -> bool {
+        c.is_ascii_alphanumeric()
+            || (!c.is_ascii()
+                && cached_regex!(WORD_CHAR_PATTERN).is_match(c.encode_utf8(&mut [0; 4])))
+    }
+
+    /// Whether `c` is part of an emoji, per [`EMOJI_PATTERN`].
+    fn is_emoji(c: char) -> bool {
+        !c.is_ascii() && cached_regex!(EMOJI_PATTERN).is_match(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// The largest char boundary in `text` that is not past `index`.
+    fn floor_char_boundary(text: &str, index: usize) -> usize {
+        let mut index = index.min(text.len());
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    /// Terminates every token with `\n` and concatenates them.
+    fn to_lines(tokens: Vec<String>) -> String {
+        let mut lines = String::with_capacity(tokens.iter().map(|t| t.len() + 1).sum());
+        for token in tokens {
+            lines.push_str(&token);
+            lines.push('\n');
+        }
+        lines
+    }
+
+    /// Returns the relation keyword that `before` (the text preceding a mention) ends with.
+    fn relation_before(before: &str) -> Option<Relation> {
+        let before = before.trim_end();
+        RELATION_KEYWORDS
+            .into_iter()
+            .find_map(|(keyword, relation)| {
+                let split = before.len().checked_sub(keyword.len())?;
+                let tail = before.get(split..)?;
+                let whole_word =
+                    !before[..split].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+                (tail.eq_ignore_ascii_case(keyword) && whole_word).then_some(relation)
+            })
+    }
+
+    /// Converts `token` to NFC, borrowing when it already is.
+    #[cfg(feature = "unicode-normalization")]
+    fn to_nfc(token: Cow<'_, str>) -> Cow<'_, str> {
+        use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+        if is_nfc(&token) {
+            token
+        } else {
+            Cow::Owned(token.nfc().collect())
+        }
+    }
+
+    /// Lowercases `token`, borrowing when that changes nothing.
+    fn to_lowercase(token: Cow<'_, str>) -> Cow<'_, str> {
+        let unchanged = token.chars().all(|c| {
+            let mut lower = c.to_lowercase();
+            lower.next() == Some(c) && lower.next().is_none()
+        });
+        if unchanged {
+            token
+        } else {
+            Cow::Owned(token.to_lowercase())
+        }
+    }
+
+    /// Folds `key` towards ASCII: decomposes it, drops the combining marks, and maps the
+    /// remaining Latin letters with [`ascii_letter`].
+    #[cfg(feature = "unicode-normalization")]
+    fn fold_diacritics(key: Cow<'_, str>) -> Cow<'_, str> {
+        use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+        if key.is_ascii() {
+            return key;
+        }
+        let mut folded = String::with_capacity(key.len());
+        for c in key.nfd().filter(|&c| !is_combining_mark(c)) {
+            match ascii_letter(c) {
+                Some(ascii) => folded.push_str(ascii),
+                None => folded.push(c),
+            }
+        }
+        Cow::Owned(folded)
+    }
+
+    /// ASCII spelling of a Latin letter that has no canonical decomposition.
+    #[cfg(feature = "unicode-normalization")]
+    fn ascii_letter(c: char) -> Option<&'static str> {
+        Some(match c {
+            'ß' => "ss",
+            'ẞ' => "SS",
+            'æ' => "ae",
+            'Æ' => "AE",
+            'œ' => "oe",
+            'Œ' => "OE",
+            'ø' => "o",
+            'Ø' => "O",
+            'ł' => "l",
+            'Ł' => "L",
+            'đ' | 'ð' => "d",
+            'Đ' | 'Ð' => "D",
+            'þ' => "th",
+            'Þ' => "TH",
+            'ħ' => "h",
+            'Ħ' => "H",
+            'ı' => "i",
+            'ŧ' => "t",
+            'Ŧ' => "T",
+            _ => return None,
+        })
+    }
+
+    /// Byte-string version of [`is_mention_start`]; invalid UTF-8 before `start` counts as a
+    /// boundary.
+    fn is_mention_start_bytes(description: &[u8], start: usize) -> bool {
+        let before = &description[..start];
+        // A UTF-8 character is at most 4 bytes; find where the last one begins.
+        let char_start = (start.saturating_sub(4)..start)
+            .rev()
+            .find(|&i| before[i] & 0xC0 != 0x80)
+            .unwrap_or(start);
+        core::str::from_utf8(&before[char_start..])
+            .ok()
+            .and_then(|c| c.chars().next())
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+    }
+
+    /// Inserts each token into `set`, allocating only for tokens it doesn't contain yet.
+    fn extend_set<'a>(set: &mut HashSet<String>, tokens: impl Iterator<Item = &'a str>) {
+        for token in tokens {
+            if !set.contains(token) {
+                set.insert(token.to_string());
+            }
+        }
+    }
+}
+
+/// The most commonly used items, for a single glob import.
+///
+/// ```
+/// use mentions_hashtags::prelude::*;
+///
+/// let result = parse_mentions_hashtags("@MrBeast new video #fyp", true, true).unwrap();
+/// assert_eq!(result.mentions, vec!["@MrBeast"]);
+/// assert_eq!(hashtags("#fyp #fyp"), vec!["#fyp"]);
+/// ```
+pub mod prelude {
+    pub use crate::mentions_hashtags::{
+        hashtags, iter_hashtags, iter_mentions, mentions, parse_hashtags, parse_mentions,
+        parse_mentions_hashtags, parse_tokens, Extractor, ExtractorBuilder, MentionsHashtags,
+        ParseError, Token,
+    };
+}
+
+/// C ABI for calling the extractor from other languages (e.g. Python via `cffi`).
+///
+/// Build a shared library with `cargo rustc --release --features ffi --crate-type cdylib`.
+///
+/// # Ownership
+/// - Input strings are borrowed: they must be NUL-terminated UTF-8 and are not freed
+/// - Every non-null string returned here is owned by the caller and must be released with
+///   [`mh_string_free`](ffi::mh_string_free) exactly once; do not pass it to C's `free`
+/// - Results use the same format as [`mentions_to_lines`](crate::mentions_hashtags::mentions_to_lines):
+///   every token, deduplicated in order of first appearance, is followed by `\n`
+///   (`"@a\n@b\n"`), so no tokens yields an empty string
+/// - Null is returned for a null pointer or input that is not valid UTF-8
+#[cfg(feature = "ffi")]
+pub mod ffi {
+    use crate::mentions_hashtags::{hashtags_to_lines, mentions_to_lines};
+    use core::ffi::c_char;
+    use std::ffi::{CStr, CString};
+
+    /// Extracts the mentions in `description`, each followed by `\n`.
+    ///
+    /// # Safety
+    /// `description` must be null or point to a NUL-terminated string that stays valid for
+    /// the duration of the call.
+    #[no_mangle]
+    pub unsafe extern "C" fn mh_parse_mentions(description: *const c_char) -> *mut c_char {
+        // SAFETY: upheld by the caller.
+        unsafe { lines(description, mentions_to_lines) }
+    }
+
+    /// Extracts the hashtags in `description`, each followed by `\n`.
+    ///
+    /// # Safety
+    /// `description` must be null or point to a NUL-terminated string that stays valid for
+    /// the duration of the call.
+    #[no_mangle]
+    pub unsafe extern "C" fn mh_parse_hashtags(description: *const c_char) -> *mut c_char {
+        // SAFETY: upheld by the caller.
+        unsafe { lines(description, hashtags_to_lines) }
+    }
+
+    /// Frees a string returned by this module. Null is ignored.
+    ///
+    /// # Safety
+    /// `s` must be null or a pointer returned by a function in this module that has not
+    /// been freed yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn mh_string_free(s: *mut c_char) {
+        if !s.is_null() {
+            // SAFETY: `s` came from `CString::into_raw` in `lines`, per the caller.
+            drop(unsafe { CString::from_raw(s) });
+        }
+    }
+
+    /// Runs `format` on the C string and returns its output as an owned C string.
+    unsafe fn lines(description: *const c_char, format: fn(&str) -> String) -> *mut c_char {
+        if description.is_null() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: `description` is non-null and NUL-terminated, per the caller.
+        let Ok(description) = unsafe { CStr::from_ptr(description) }.to_str() else {
+            return core::ptr::null_mut();
+        };
+        // Tokens never contain NUL (it is neither a letter nor an allowed character).
+        CString::new(format(description)).map_or(core::ptr::null_mut(), CString::into_raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mentions_hashtags::*;
+    // Match the collection types the public API uses in each configuration.
+    #[cfg(not(feature = "std"))]
+    use std::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+    #[cfg(feature = "std")]
+    use std::collections::{HashMap, HashSet};
+
+    // === Mentions Tests ===
+    #[test]
+    fn test_youtube_mentions() {
+        let result = parse_mentions("@MrBeast @EmmaChamberlain @PewDiePie").unwrap();
+        let expected: HashSet<_> = ["@MrBeast", "@EmmaChamberlain", "@PewDiePie"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
+    #[test]
+    fn test_tiktok_mentions_with_duplicates() {
+        let result = parse_mentions("@charlidamelio @charlidamelio @Khaby.Lame").unwrap();
+        let expected: HashSet<_> = ["@charlidamelio", "@Khaby.Lame"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
+    #[test]
+    fn test_mentions_case_insensitive_but_preserved() {
+        let result = parse_mentions("@AddisonRae @addisonrae").unwrap();
+        assert!(result.contains(&"@AddisonRae".to_string()));
+        assert!(result.contains(&"@addisonrae".to_string()));
+        assert_eq!(result.len(), 2); // preserves case
+    }
+
+    #[test]
+    fn test_mentions_empty_input() {
+        let result = parse_mentions("").unwrap();
+        assert!(result.is_empty());
+    }
+
+    // === Hashtags Tests ===
+    #[test]
+    fn test_tiktok_hashtags_trending() {
+        let result = parse_hashtags("#fyp #trending #CapCut #viral").unwrap();
+        let expected: HashSet<_> = ["#fyp", "#trending", "#CapCut", "#viral"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
+    #[test]
+    fn test_youtube_hashtags_mixed_case() {
+        let result = parse_hashtags("#Shorts #YouTubeShorts #Music #music").unwrap();
+        let expected: HashSet<_> = ["#Shorts", "#YouTubeShorts", "#Music", "#music"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
+    #[test]
+    fn test_hashtags_with_punctuation() {
+        let result = parse_hashtags("Try this! #Challenge-2025 #fun.time #go_crazy.").unwrap();
+        let expected: HashSet<_> = ["#Challenge-2025", "#fun.time", "#go_crazy."]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(result.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
+    #[test]
+    fn test_hashtags_empty_input() {
+        let result = parse_hashtags("").unwrap();
+        assert!(result.is_empty());
+    }
+
+    // === Custom Charset ===
+    #[test]
+    fn test_mentions_twitter_charset() {
+        let result = parse_mentions_with_charset("@user.name @jack_dorsey @a-b", "_").unwrap();
+        assert_eq!(result, vec!["@user", "@jack_dorsey", "@a"]);
+    }
+
+    #[test]
+    fn test_default_charset_matches_default_parser() {
+        let text = "@Khaby.Lame @go-go #fun.time #Challenge-2025 #go_crazy.";
+        assert_eq!(
+            parse_mentions_with_charset(text, DEFAULT_TOKEN_CHARS).unwrap(),
+            parse_mentions(text).unwrap()
+        );
+        assert_eq!(
+            parse_hashtags_with_charset(text, DEFAULT_TOKEN_CHARS).unwrap(),
+            parse_hashtags(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_charset_metacharacters_are_escaped() {
+        let result = parse_hashtags_with_charset("#a]b #c^d #e\\f #g-h #i", "]^\\").unwrap();
+        assert_eq!(result, vec!["#a]b", "#c^d", "#e\\f", "#g", "#i"]);
+        assert_eq!(
+            parse_hashtags_with_charset("#a.b #c", "").unwrap(),
+            vec!["#a", "#c"]
+        );
+    }
+
+    // === Cashtags ===
+    #[test]
+    fn test_cashtags_basic() {
+        let result = parse_cashtags("Buying $AAPL and $TSLA, selling $btc").unwrap();
+        assert_eq!(result, vec!["$AAPL", "$TSLA", "$btc"]);
+    }
+
+    #[test]
+    fn test_cashtags_skip_amounts() {
+        assert!(cashtags("It cost $100 or $9.99").is_empty());
+        assert_eq!(cashtags("$1 vs $A1"), vec!["$A1"]);
+    }
+
+    #[test]
+    fn test_cashtags_dedup_preserves_case() {
+        let result = cashtags("$BRK.B $brk.b $BRK.B $ETH-USD");
+        assert_eq!(result, vec!["$BRK.B", "$brk.b", "$ETH-USD"]);
+    }
+
+    // === Combined Parser ===
+    #[test]
+    fn test_parse_both_mentions_and_hashtags() {
+        let result = parse_mentions_hashtags(
+            "@MrBeast just posted a new video! #fyp #MrBeastChallenge",
             true,
             true,
         )
         .unwrap();
 
-        assert!(result.mentions.contains(&"@MrBeast".to_string()));
-        assert!(result.hashtags.contains(&"#fyp".to_string()));
-        assert!(result.hashtags.contains(&"#MrBeastChallenge".to_string()));
+        assert!(result.mentions.contains(&"@MrBeast".to_string()));
+        assert!(result.hashtags.contains(&"#fyp".to_string()));
+        assert!(result.hashtags.contains(&"#MrBeastChallenge".to_string()));
+    }
+
+    #[test]
+    fn test_parse_none_enabled() {
+        let result = parse_mentions_hashtags("@Khaby.Lame #viral", false, false).unwrap();
+        assert!(result.mentions.is_empty());
+        assert!(result.hashtags.is_empty());
+    }
+
+    // === Infallible API ===
+    #[test]
+    fn test_infallible_matches_fallible() {
+        let text = "@MrBeast @EmmaChamberlain #fyp #Shorts @MrBeast";
+        assert_eq!(mentions(text), parse_mentions(text).unwrap());
+        assert_eq!(hashtags(text), parse_hashtags(text).unwrap());
+    }
+
+    #[test]
+    fn test_infallible_in_iterator_chain() {
+        let captions = ["@a #x", "@b #y #x", ""];
+        let all: Vec<String> = captions.iter().flat_map(|c| hashtags(c)).collect();
+        assert_eq!(all, vec!["#x", "#y", "#x"]);
+        assert!(mentions("").is_empty());
+    }
+
+    #[test]
+    fn test_combined_single_pass_matches_separate_parsers() {
+        let text = "@MrBeast #fyp@Khaby.Lame #viral @#odd #fyp @MrBeast";
+        let result = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(result.mentions, parse_mentions(text).unwrap());
+        assert_eq!(result.hashtags, parse_hashtags(text).unwrap());
+    }
+
+    #[test]
+    fn test_combined_respects_flags() {
+        let text = "@MrBeast #fyp";
+        let only_mentions = parse_mentions_hashtags(text, true, false).unwrap();
+        assert_eq!(only_mentions.mentions, vec!["@MrBeast"]);
+        assert!(only_mentions.hashtags.is_empty());
+
+        let only_hashtags = parse_mentions_hashtags(text, false, true).unwrap();
+        assert!(only_hashtags.mentions.is_empty());
+        assert_eq!(only_hashtags.hashtags, vec!["#fyp"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_shape() {
+        let result = parse_mentions_hashtags("@MrBeast #fyp #viral", true, true).unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"mentions": ["@MrBeast"], "hashtags": ["#fyp", "#viral"]})
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_round_trip() {
+        let json = r##"{"mentions":["@a"],"hashtags":["#b","#c"]}"##;
+        let parsed: MentionsHashtags = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.mentions, vec!["@a"]);
+        assert_eq!(parsed.hashtags, vec!["#b", "#c"]);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_token_span_shape() {
+        let json = serde_json::to_value(parse_token_spans("@x #y")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"kind": "mention", "text": "@x", "start": 0, "end": 2},
+                {"kind": "hashtag", "text": "#y", "start": 3, "end": 5},
+            ])
+        );
+        let token = serde_json::to_value(Token::Mention("@x".to_string())).unwrap();
+        assert_eq!(token, serde_json::json!({"kind": "mention", "text": "@x"}));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_token_types_round_trip() {
+        let spans = parse_token_spans("@a #b\n#c");
+        let back: Vec<TokenSpan> =
+            serde_json::from_str(&serde_json::to_string(&spans).unwrap()).unwrap();
+        assert_eq!(back, spans);
+
+        let token: Token = serde_json::from_str(r##"{"kind":"hashtag","text":"#fyp"}"##).unwrap();
+        assert_eq!(token, Token::Hashtag("#fyp".to_string()));
+
+        let locations = parse_with_locations("x\n  @a");
+        let back: Vec<TokenLocation> =
+            serde_json::from_str(&serde_json::to_string(&locations).unwrap()).unwrap();
+        assert_eq!(back, locations);
+    }
+
+    // === Ordering ===
+    #[test]
+    fn test_mentions_keep_first_seen_order() {
+        let result = parse_mentions("@zack @amy @zack @Bob @amy").unwrap();
+        assert_eq!(result, vec!["@zack", "@amy", "@Bob"]);
+    }
+
+    #[test]
+    fn test_hashtags_keep_first_seen_order() {
+        let result = parse_hashtags("#viral #fyp #CapCut #fyp #viral").unwrap();
+        assert_eq!(result, vec!["#viral", "#fyp", "#CapCut"]);
+    }
+
+    #[test]
+    fn test_combined_keeps_first_seen_order() {
+        let result = parse_mentions_hashtags("@b #y @a #x @b #y", true, true).unwrap();
+        assert_eq!(result.mentions, vec!["@b", "@a"]);
+        assert_eq!(result.hashtags, vec!["#y", "#x"]);
+    }
+
+    // === Bare Tokens ===
+    #[test]
+    fn test_mentions_bare_strips_sigil() {
+        let result = parse_mentions_bare("@MrBeast @charlidamelio @MrBeast").unwrap();
+        assert_eq!(result, vec!["MrBeast", "charlidamelio"]);
+    }
+
+    #[test]
+    fn test_hashtags_bare_keeps_case_distinct() {
+        let result = parse_hashtags_bare("#Music #music #go_crazy.").unwrap();
+        assert_eq!(result, vec!["Music", "music", "go_crazy."]);
+    }
+
+    #[test]
+    fn test_bare_empty_input() {
+        assert!(parse_mentions_bare("").unwrap().is_empty());
+        assert!(parse_hashtags_bare("no tags here").unwrap().is_empty());
+    }
+
+    // === Trimmed Tokens ===
+    #[test]
+    fn test_hashtags_trimmed_strips_trailing_punctuation() {
+        let result =
+            parse_hashtags_trimmed("Try this! #Challenge-2025. #fun.time #go_crazy.").unwrap();
+        assert_eq!(result, vec!["#Challenge-2025", "#fun.time", "#go_crazy"]);
+    }
+
+    #[test]
+    fn test_mentions_trimmed_end_of_sentence() {
+        let result = parse_mentions_trimmed("thanks @MrBeast. See you @Khaby.Lame-").unwrap();
+        assert_eq!(result, vec!["@MrBeast", "@Khaby.Lame"]);
+    }
+
+    #[test]
+    fn test_trimmed_drops_punctuation_only_tokens() {
+        assert!(parse_hashtags_trimmed("#. #-- #.-.").unwrap().is_empty());
+        assert!(parse_mentions_trimmed("@... @-").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trimmed_dedups_after_trimming() {
+        let result = parse_hashtags_trimmed("#fyp #fyp. #fyp-").unwrap();
+        assert_eq!(result, vec!["#fyp"]);
+    }
+
+    // === Case-Insensitive Dedup ===
+    #[test]
+    fn test_mentions_dedup_ci_keeps_first_casing() {
+        let result = parse_mentions_dedup_ci("@AddisonRae @addisonrae").unwrap();
+        assert_eq!(result, vec!["@AddisonRae"]);
+    }
+
+    #[test]
+    fn test_mentions_dedup_ci_first_seen_wins() {
+        let result = parse_mentions_dedup_ci("@pewdiepie @PewDiePie @MrBeast @MRBEAST").unwrap();
+        assert_eq!(result, vec!["@pewdiepie", "@MrBeast"]);
+    }
+
+    #[test]
+    fn test_hashtags_dedup_ci() {
+        let result = parse_hashtags_dedup_ci("#Shorts #shorts #Music #music").unwrap();
+        assert_eq!(result, vec!["#Shorts", "#Music"]);
+    }
+
+    // === Spans ===
+    #[test]
+    fn test_mention_spans_keep_every_occurrence() {
+        let text = "@MrBeast hi @Khaby.Lame and @MrBeast";
+        let result = parse_mentions_spans(text).unwrap();
+        assert_eq!(result.len(), 3);
+        for (mention, start, end) in &result {
+            assert_eq!(&text[*start..*end], mention);
+        }
+        assert_eq!(result[0].1, 0);
+        assert_eq!(result[2].1, 28);
+    }
+
+    #[test]
+    fn test_hashtag_spans_multibyte_prefix() {
+        let text = "🔥 #fyp";
+        let result = parse_hashtags_spans(text).unwrap();
+        assert_eq!(result, vec![("#fyp".to_string(), 5, 9)]);
+    }
+
+    #[test]
+    fn test_spans_empty_input() {
+        assert!(parse_mentions_spans("").unwrap().is_empty());
+        assert!(parse_hashtags_spans("").unwrap().is_empty());
+    }
+
+    // === Extractor ===
+    #[test]
+    fn test_extractor_defaults_match_combined_parser() {
+        let long = format!("#{}", "a".repeat(MAX_TOKEN_LEN + 1));
+        let text = format!("@MrBeast @mrbeast #fyp #go_crazy. #fyp @Khaby.Lame {long}");
+        let text = text.as_str();
+        // Trailing punctuation trimming is the one default the free functions don't share.
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(false)
+            .build()
+            .unwrap();
+        let expected = parse_mentions_hashtags(text, true, true).unwrap();
+        let result = extractor.parse(text);
+        assert_eq!(result.mentions, expected.mentions);
+        assert_eq!(result.hashtags, expected.hashtags);
+    }
+
+    #[test]
+    fn test_extractor_trims_trailing_punct_by_default() {
+        let text = "@MrBeast. #go_crazy. #fyp- #keep.it";
+        let result = Extractor::builder().build().unwrap().parse(text);
+        assert_eq!(result.mentions, vec!["@MrBeast"]);
+        assert_eq!(result.hashtags, vec!["#go_crazy", "#fyp", "#keep.it"]);
+        let expected = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(expected.hashtags, vec!["#go_crazy.", "#fyp-", "#keep.it"]);
+    }
+
+    #[test]
+    fn test_extractor_trim_and_case_insensitive() {
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .case_insensitive_dedup(true)
+            .build()
+            .unwrap();
+        let result = extractor.parse("hi @MrBeast. @mrbeast #Fyp- #fyp #- #go_crazy.");
+        assert_eq!(result.mentions, vec!["@MrBeast"]);
+        assert_eq!(result.hashtags, vec!["#Fyp", "#go_crazy"]);
+    }
+
+    #[test]
+    fn test_extractor_kind_toggles_and_charset() {
+        let extractor = Extractor::builder()
+            .hashtags(false)
+            .mention_chars("_")
+            .build()
+            .unwrap();
+        let result = extractor.parse("@user.name @jack_dorsey #fyp");
+        assert_eq!(result.mentions, vec!["@user", "@jack_dorsey"]);
+        assert!(result.hashtags.is_empty());
+
+        let none = Extractor::builder()
+            .mentions(false)
+            .hashtags(false)
+            .build()
+            .unwrap();
+        assert!(none.parse("@a #b").mentions.is_empty());
+    }
+
+    #[test]
+    fn test_extractor_is_reusable() {
+        let extractor = ExtractorBuilder::new().build().unwrap();
+        assert_eq!(extractor.parse("#a #b").hashtags, vec!["#a", "#b"]);
+        assert_eq!(extractor.parse("@c #a").mentions, vec!["@c"]);
+        assert_eq!(extractor.parse("").hashtags, Vec::<String>::new());
+    }
+
+    // === Email Disambiguation ===
+    #[test]
+    fn test_email_is_not_a_mention() {
+        assert!(parse_mentions("contact me at john@example.com")
+            .unwrap()
+            .is_empty());
+        assert!(parse_mentions("a@b").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mention_after_whitespace_or_punctuation() {
+        let result = parse_mentions("hey @user, (@other) and \"@quoted\" @@double").unwrap();
+        assert_eq!(result, vec!["@user", "@other", "@quoted", "@double"]);
+        assert_eq!(mentions("@start"), vec!["@start"]);
+    }
+
+    #[test]
+    fn test_email_skipped_in_every_mention_path() {
+        let text = "mail john@example.com or ping @MrBeast.";
+        assert_eq!(
+            parse_mentions_spans(text).unwrap(),
+            vec![("@MrBeast.".to_string(), 30, 39)]
+        );
+        assert_eq!(parse_mentions_trimmed(text).unwrap(), vec!["@MrBeast"]);
+        assert_eq!(parse_mentions_dedup_ci(text).unwrap(), vec!["@MrBeast."]);
+        assert_eq!(
+            parse_mentions_with_charset(text, "_").unwrap(),
+            vec!["@MrBeast"]
+        );
+        assert_eq!(
+            parse_mentions_hashtags(text, true, false).unwrap().mentions,
+            vec!["@MrBeast."]
+        );
+        let extractor = Extractor::builder().build().unwrap();
+        assert_eq!(extractor.parse(text).mentions, vec!["@MrBeast"]);
+    }
+
+    // === Unicode ===
+    #[test]
+    fn test_unicode_hashtags() {
+        let result = hashtags("#café #日本語 #Москва #fyp");
+        assert_eq!(result, vec!["#café", "#日本語", "#Москва", "#fyp"]);
+    }
+
+    #[test]
+    fn test_unicode_mentions() {
+        let result = mentions("@José_García @Дмитрий @山田.太郎");
+        assert_eq!(result, vec!["@José_García", "@Дмитрий", "@山田.太郎"]);
+    }
+
+    #[test]
+    fn test_unicode_combining_marks_and_spans() {
+        let decomposed = "#cafe\u{301} done";
+        assert_eq!(hashtags(decomposed), vec!["#cafe\u{301}"]);
+        let spans = parse_hashtags_spans("¡Hola! #España").unwrap();
+        assert_eq!(spans, vec![("#España".to_string(), 8, 16)]);
+    }
+
+    #[test]
+    fn test_unicode_word_before_at_is_not_a_mention() {
+        assert!(mentions("josé@correo.es").is_empty());
+        assert_eq!(
+            parse_mentions_with_charset("@Zoë.x @Zoë-y", ".").unwrap(),
+            vec!["@Zoë.x", "@Zoë"]
+        );
+    }
+
+    // === Counts ===
+    #[test]
+    fn test_count_hashtags() {
+        let counts = count_hashtags("#fyp #fyp #viral").unwrap();
+        let expected: HashMap<String, usize> = [("#fyp".to_string(), 2), ("#viral".to_string(), 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn test_count_mentions_case_sensitive_and_skips_emails() {
+        let counts = count_mentions("@Bob @bob @Bob bob@mail.com").unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["@Bob"], 2);
+        assert_eq!(counts["@bob"], 1);
+    }
+
+    #[test]
+    fn test_counts_empty_input() {
+        assert!(count_mentions("").unwrap().is_empty());
+        assert!(count_hashtags("no tags").unwrap().is_empty());
+    }
+
+    // === Iterators ===
+    #[test]
+    fn test_iter_mentions_keeps_duplicates_and_borrows() {
+        let text = String::from("@a @b @a me@mail.com");
+        let found: Vec<&str> = iter_mentions(&text).collect();
+        assert_eq!(found, vec!["@a", "@b", "@a"]);
+        assert!(std::ptr::eq(found[0].as_ptr(), text.as_ptr()));
+    }
+
+    #[test]
+    fn test_iter_hashtags_short_circuit() {
+        let mut iter = iter_hashtags("#one #two #three");
+        assert_eq!(iter.next(), Some("#one"));
+        assert_eq!(iter.find(|t| t.ends_with("ee")), Some("#three"));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter_hashtags("").count(), 0);
+    }
+
+    // === Length Bounds ===
+    #[test]
+    fn test_extractor_min_and_max_len() {
+        let long = format!("#{}", "x".repeat(300));
+        let text = format!("#a #ok @b @bob {long}");
+        let extractor = Extractor::builder().min_len(2).max_len(30).build().unwrap();
+        let result = extractor.parse(&text);
+        assert_eq!(result.hashtags, vec!["#ok"]);
+        assert_eq!(result.mentions, vec!["@bob"]);
+    }
+
+    #[test]
+    fn test_extractor_len_counts_chars_not_bytes() {
+        let extractor = Extractor::builder().max_len(3).build().unwrap();
+        assert_eq!(
+            extractor.parse("#日本語 #日本語です").hashtags,
+            vec!["#日本語"]
+        );
+        let min = Extractor::builder().min_len(3).build().unwrap();
+        assert_eq!(min.parse("#日本 #café").hashtags, vec!["#café"]);
+    }
+
+    #[test]
+    fn test_extractor_len_applies_after_trimming() {
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .max_len(3)
+            .build()
+            .unwrap();
+        assert_eq!(extractor.parse("#fyp...").hashtags, vec!["#fyp"]);
+    }
+
+    // === Require Alpha ===
+    #[test]
+    fn test_require_alpha_drops_digit_and_punctuation_tokens() {
+        let extractor = Extractor::builder().require_alpha(true).build().unwrap();
+        let result = extractor.parse("#2025 #Challenge2025 #___ #_a_ @_._ @123 @user1 #日本");
+        assert_eq!(result.hashtags, vec!["#Challenge2025", "#_a_", "#日本"]);
+        assert_eq!(result.mentions, vec!["@user1"]);
+    }
+
+    #[test]
+    fn test_require_alpha_off_by_default() {
+        let extractor = Extractor::builder().build().unwrap();
+        assert_eq!(
+            extractor.parse("#2025 #___").hashtags,
+            vec!["#2025", "#___"]
+        );
+    }
+
+    // === Linkify ===
+    #[test]
+    fn test_linkify_rewrites_in_place() {
+        let html = linkify(
+            "Hi @MrBeast! Loved it #fyp #fyp",
+            |u| format!("/u/{u}"),
+            |t| format!("/t/{t}"),
+        );
+        assert_eq!(
+            html,
+            r#"Hi <a href="/u/MrBeast">@MrBeast</a>! Loved it <a href="/t/fyp">#fyp</a> <a href="/t/fyp">#fyp</a>"#
+        );
+    }
+
+    #[test]
+    fn test_linkify_leaves_plain_text_and_emails() {
+        let text = "mail me@example.com, no tags <b>here</b>";
+        assert_eq!(linkify(text, |u| u.to_string(), |t| t.to_string()), text);
+        assert_eq!(linkify("", |u| u.to_string(), |t| t.to_string()), "");
+    }
+
+    // === Strip ===
+    #[test]
+    fn test_strip_both_kinds() {
+        assert_eq!(
+            strip("@MrBeast new video #fyp #viral", true, true),
+            "new video"
+        );
+        assert_eq!(strip("#a #b #c", true, true), "");
+    }
+
+    #[test]
+    fn test_strip_collapses_whitespace_between_tokens() {
+        assert_eq!(strip("so #fyp #viral good", false, true), "so good");
+        assert_eq!(strip("wow#fyp nice", false, true), "wow nice");
+        assert_eq!(strip("  hi @a\n\n#b  there ", true, true), "hi there");
+    }
+
+    #[test]
+    fn test_strip_respects_flags_and_emails() {
+        let text = "@MrBeast mail me@example.com #fyp";
+        assert_eq!(strip(text, true, false), "mail me@example.com #fyp");
+        assert_eq!(strip(text, false, false), text);
+    }
+
+    // === Borrowed Sets ===
+    #[test]
+    fn test_mentions_borrowed_matches_owned() {
+        let text = "@MrBeast @EmmaChamberlain @MrBeast john@mail.com";
+        let borrowed = parse_mentions_borrowed(text).unwrap();
+        let owned: HashSet<String> = parse_mentions(text).unwrap().into_iter().collect();
+        assert_eq!(borrowed.len(), owned.len());
+        assert!(borrowed.iter().all(|m| owned.contains(*m)));
+    }
+
+    #[test]
+    fn test_hashtags_borrowed_slices_point_into_input() {
+        let text = String::from("#fyp #Fyp");
+        let set = parse_hashtags_borrowed(&text).unwrap();
+        assert_eq!(set, ["#fyp", "#Fyp"].into_iter().collect());
+        let range = text.as_bytes().as_ptr_range();
+        assert!(set.iter().all(|t| range.contains(&t.as_ptr())));
+    }
+
+    // === Errors ===
+    #[test]
+    fn test_parse_error_from_regex_error() {
+        let unclosed = String::from("(");
+        let err: ParseError = regex::Regex::new(&unclosed).unwrap_err().into();
+        assert!(matches!(err, ParseError::Regex(_)));
+        assert!(err.to_string().starts_with("invalid pattern: "));
+        // `regex::Error` only implements `Error` with `regex/std`, so only then is it a source.
+        #[cfg(feature = "std")]
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_parse_error_in_signatures() {
+        fn run(text: &str) -> Result<usize, ParseError> {
+            let mentions = parse_mentions(text)?;
+            let extractor = Extractor::builder().mention_chars("_").build()?;
+            Ok(mentions.len() + extractor.parse(text).hashtags.len())
+        }
+        assert_eq!(run("@a #b"), Ok(2));
+    }
+
+    // === Tokens ===
+    #[test]
+    fn test_parse_tokens_interleaved_in_source_order() {
+        let tokens = parse_tokens("@a #x @b #x me@mail.com");
+        let kinds: Vec<_> = tokens.iter().map(|(t, _, _)| t.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Token::Mention("@a".to_string()),
+                Token::Hashtag("#x".to_string()),
+                Token::Mention("@b".to_string()),
+                Token::Hashtag("#x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tokens_spans_slice_input() {
+        let text = "🔥 #fyp @MrBeast";
+        for (token, start, end) in parse_tokens(text) {
+            assert_eq!(&text[start..end], token.as_str());
+        }
+        assert!(parse_tokens("").is_empty());
+    }
+
+    // === Custom Sigils ===
+    #[test]
+    fn test_parse_with_sigil_custom_markers() {
+        assert_eq!(
+            parse_with_sigil("~alice ~bob ~alice", '~').unwrap(),
+            vec!["~alice", "~bob"]
+        );
+        assert_eq!(
+            parse_with_sigil("+Larry a+b C++", '+').unwrap(),
+            vec!["+Larry"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_sigil_matches_builtin_parsers() {
+        let text = "@MrBeast #fyp me@mail.com wow#tag @Khaby.Lame";
+        assert_eq!(parse_with_sigil(text, '@').unwrap(), mentions(text));
+        assert_eq!(parse_with_sigil(text, '#').unwrap(), hashtags(text));
+    }
+
+    #[test]
+    fn test_parse_with_sigil_regex_metacharacter() {
+        assert_eq!(parse_with_sigil("^top x^no", '^').unwrap(), vec!["^top"]);
+        assert_eq!(parse_with_sigil("*star *", '*').unwrap(), vec!["*star"]);
+    }
+
+    // === Batch ===
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parse_batch_preserves_input_order() {
+        let captions: Vec<String> = (0..500).map(|i| format!("@user{i} #tag{i}")).collect();
+        let refs: Vec<&str> = captions.iter().map(String::as_str).collect();
+        let results = parse_batch(&refs, true, true);
+        assert_eq!(results.len(), 500);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.mentions, vec![format!("@user{i}")]);
+            assert_eq!(result.hashtags, vec![format!("#tag{i}")]);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parse_batch_flags_and_empty() {
+        assert!(parse_batch(&[], true, true).is_empty());
+        let results = parse_batch(&["@a #b"], false, true);
+        assert!(results[0].mentions.is_empty());
+        assert_eq!(results[0].hashtags, vec!["#b"]);
+    }
+
+    // === Aggregation ===
+    #[test]
+    fn test_aggregate_hashtags_by_texts() {
+        let texts = ["#fyp #fyp #viral", "#fyp", "no tags", "#Viral"];
+        let counts = aggregate_hashtags(&texts, AggregateMode::Texts);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["#fyp"], 2);
+        assert_eq!(counts["#viral"], 1);
+        assert_eq!(counts["#Viral"], 1);
+    }
+
+    #[test]
+    fn test_aggregate_by_occurrences() {
+        let texts = ["#fyp #fyp #viral", "#fyp @a me@mail.com", "@a @a"];
+        let hashtag_counts = aggregate_hashtags(&texts, AggregateMode::Occurrences);
+        assert_eq!(hashtag_counts["#fyp"], 3);
+        let mention_counts = aggregate_mentions(&texts, AggregateMode::Occurrences);
+        assert_eq!(mention_counts.len(), 1);
+        assert_eq!(mention_counts["@a"], 3);
+        assert!(aggregate_mentions(&[], AggregateMode::default()).is_empty());
+    }
+
+    // === Limit ===
+    #[test]
+    fn test_limit_counts_unique_tokens() {
+        let extractor = Extractor::builder().limit(2).build().unwrap();
+        let result = extractor.parse("#a #a #b #a #c #d @x @x @y @z");
+        assert_eq!(result.hashtags, vec!["#a", "#b"]);
+        assert_eq!(result.mentions, vec!["@x", "@y"]);
+    }
+
+    #[test]
+    fn test_limit_applies_after_case_dedup_and_validation() {
+        let extractor = Extractor::builder()
+            .case_insensitive_dedup(true)
+            .require_alpha(true)
+            .limit(2)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#Fyp #2025 #fyp #FYP #viral #late");
+        assert_eq!(result.hashtags, vec!["#Fyp", "#viral"]);
+    }
+
+    #[test]
+    fn test_limit_spam_caption() {
+        let spam: String = (0..5000).map(|i| format!("#tag{i} ")).collect();
+        let extractor = Extractor::builder()
+            .mentions(false)
+            .limit(30)
+            .build()
+            .unwrap();
+        let result = extractor.parse(&spam);
+        assert_eq!(result.hashtags.len(), 30);
+        assert_eq!(result.hashtags[29], "#tag29");
+        assert!(Extractor::builder()
+            .limit(0)
+            .build()
+            .unwrap()
+            .parse("#a @b")
+            .hashtags
+            .is_empty());
+    }
+
+    // === Platforms ===
+    #[test]
+    fn test_twitter_handle_rules() {
+        let text = "@jack @a @123 @abc_123 @sixteen_chars_xx @TwitterSupport @myadmin @user.name";
+        assert_eq!(
+            parse_mentions_for(text, Platform::Twitter),
+            vec!["@jack", "@a", "@abc_123", "@user"]
+        );
+    }
+
+    #[test]
+    fn test_instagram_handle_rules() {
+        let thirty_one = format!("@{}", "a".repeat(31));
+        let text = format!(
+            "@foo.bar @foo..bar @.foo @under_score @{} {thirty_one}",
+            "b".repeat(30)
+        );
+        assert_eq!(
+            parse_mentions_for(&text, Platform::Instagram),
+            vec![
+                "@foo.bar".to_string(),
+                "@under_score".to_string(),
+                format!("@{}", "b".repeat(30))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_platform_handles_before_sentence_period() {
+        let text = "great shot by @natgeo. Also @foo.bar... and @a..b.";
+        assert_eq!(
+            parse_mentions_for(text, Platform::Instagram),
+            vec!["@natgeo", "@foo.bar"]
+        );
+        // TikTok allows consecutive dots, just not a trailing one.
+        assert_eq!(
+            parse_mentions_for(text, Platform::TikTok),
+            vec!["@natgeo", "@foo.bar", "@a..b"]
+        );
+        assert_eq!(
+            parse_mentions_for(text, Platform::Twitter),
+            vec!["@natgeo", "@foo", "@a"]
+        );
+    }
+
+    #[test]
+    fn test_platform_handles_not_truncated_at_non_ascii() {
+        let text = "hola @josé y @ana. @te\u{301}st @zoë_x @ok";
+        for platform in [Platform::Twitter, Platform::Instagram, Platform::TikTok] {
+            assert_eq!(parse_mentions_for(text, platform), vec!["@ana", "@ok"]);
+        }
+        assert_eq!(
+            parse_mentions_for("@josé", Platform::Generic),
+            vec!["@josé"]
+        );
+    }
+
+    #[test]
+    fn test_tiktok_and_generic_rules() {
+        assert_eq!(
+            parse_mentions_for("@a @ab @khaby.lame @Дмитрий", Platform::TikTok),
+            vec!["@ab", "@khaby.lame"]
+        );
+        let text = "@Khaby.Lame @Дмитрий me@mail.com";
+        assert_eq!(parse_mentions_for(text, Platform::Generic), mentions(text));
+        assert!(Platform::Generic.is_valid_handle("Дмитрий"));
+        assert!(!Platform::Generic.is_valid_handle(""));
+        assert!(!Platform::Generic.is_valid_handle("a b"));
+        assert!(!Platform::Generic.is_valid_handle("-foo"));
+    }
+
+    // === Dot policy ===
+    #[test]
+    fn test_dot_policy_reject() {
+        let extractor = Extractor::builder()
+            .dots(DotPolicy::Reject)
+            .trim_trailing_punct(false)
+            .build()
+            .unwrap();
+        let result = extractor.parse("@foo. @foo..bar @foo.bar #tag..x");
+        assert_eq!(result.mentions, vec!["@foo.bar"]);
+        assert_eq!(result.hashtags, vec!["#tag..x"]);
+    }
+
+    #[test]
+    fn test_dot_policy_truncate() {
+        let extractor = Extractor::builder()
+            .dots(DotPolicy::Truncate)
+            .build()
+            .unwrap();
+        let result = extractor.parse("@foo. @foo..bar @foo.bar @... @bar...");
+        assert_eq!(result.mentions, vec!["@foo", "@foo.bar", "@bar"]);
+    }
+
+    #[test]
+    fn test_dot_policy_allow_is_default() {
+        let result = Extractor::builder()
+            .trim_trailing_punct(false)
+            .build()
+            .unwrap()
+            .parse("@foo. @foo..bar");
+        assert_eq!(result.mentions, vec!["@foo.", "@foo..bar"]);
+    }
+
+    // === Public patterns ===
+    #[test]
+    fn test_patterns_match_parse_results() {
+        let text = "Hi @Alice and @bob_1, #Rust #日本";
+        let raw: Vec<&str> = mention_regex()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .collect();
+        assert_eq!(raw, parse_mentions(text).unwrap());
+        let raw: Vec<&str> = hashtag_regex()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .collect();
+        assert_eq!(raw, parse_hashtags(text).unwrap());
+    }
+
+    #[test]
+    fn test_patterns_embed_in_alternation() {
+        let re = regex::Regex::new(&format!(
+            "(?:{MENTION_PATTERN})|(?:{HASHTAG_PATTERN})|[0-9]+"
+        ))
+        .unwrap();
+        let found: Vec<&str> = re.find_iter("@A 42 #B").map(|m| m.as_str()).collect();
+        assert_eq!(found, vec!["@A", "42", "#B"]);
+        assert_eq!(mention_regex().as_str(), MENTION_PATTERN);
+    }
+
+    // === Full-width sigils ===
+    #[test]
+    fn test_fullwidth_sigils_are_matched_as_written() {
+        let text = "Thanks ＠MrBeast for ＃challenge and @MrBeast #challenge";
+        assert_eq!(mentions(text), vec!["＠MrBeast", "@MrBeast"]);
+        assert_eq!(hashtags(text), vec!["＃challenge", "#challenge"]);
+        let result = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(result.mentions, vec!["＠MrBeast", "@MrBeast"]);
+        assert_eq!(result.hashtags, vec!["＃challenge", "#challenge"]);
+    }
+
+    #[test]
+    fn test_fullwidth_sigils_in_other_apis() {
+        let text = "me＠mail.com ＠ユーザー ＃日本";
+        assert_eq!(mentions(text), vec!["＠ユーザー"]);
+        assert_eq!(parse_mentions_bare(text).unwrap(), vec!["ユーザー"]);
+        assert_eq!(
+            parse_mentions_spans(text).unwrap(),
+            vec![("＠ユーザー".to_string(), 14, 29)]
+        );
+        assert_eq!(strip(text, true, true), "me＠mail.com");
+        let tokens: Vec<Token> = parse_tokens(text).into_iter().map(|t| t.0).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Mention("＠ユーザー".to_string()),
+                Token::Hashtag("＃日本".to_string())
+            ]
+        );
+        let result = Extractor::builder().build().unwrap().parse(text);
+        assert_eq!(result.mentions, vec!["＠ユーザー"]);
+        assert_eq!(result.hashtags, vec!["＃日本"]);
+    }
+
+    // === Totals ===
+    #[test]
+    fn test_count_totals_include_duplicates() {
+        let text = "#fyp @a #fyp @a #viral john@example.com";
+        assert_eq!(count_hashtags_total(text), 3);
+        assert_eq!(count_mentions_total(text), 2);
+        assert_eq!(count_hashtags_total(""), 0);
+    }
+
+    #[test]
+    fn test_count_totals_match_counts() {
+        let text = "@x @y @x #a #b #a #a";
+        let mentions_sum: usize = count_mentions(text).unwrap().values().sum();
+        let hashtags_sum: usize = count_hashtags(text).unwrap().values().sum();
+        assert_eq!(count_mentions_total(text), mentions_sum);
+        assert_eq!(count_hashtags_total(text), hashtags_sum);
+    }
+
+    // === Case normalization ===
+    #[test]
+    fn test_normalize_case_lowercases_output() {
+        let extractor = Extractor::builder().normalize_case(true).build().unwrap();
+        let result = extractor.parse("#Music #music #MUSIC @AddisonRae @addisonrae");
+        assert_eq!(result.hashtags, vec!["#music"]);
+        assert_eq!(result.mentions, vec!["@addisonrae"]);
+    }
+
+    #[test]
+    fn test_normalize_case_is_unicode_aware() {
+        let extractor = Extractor::builder().normalize_case(true).build().unwrap();
+        let result = extractor.parse("#ÉTÉ #été @ДМИТРИЙ");
+        assert_eq!(result.hashtags, vec!["#été"]);
+        assert_eq!(result.mentions, vec!["@дмитрий"]);
+    }
+
+    // === Emails ===
+    #[test]
+    fn test_emails_are_extracted_separately_from_mentions() {
+        let text = "Contact john@example.com or @john. Also JOHN@example.com, john@example.com";
+        assert_eq!(
+            parse_emails(text).unwrap(),
+            vec!["john@example.com", "JOHN@example.com"]
+        );
+        assert_eq!(mentions(text), vec!["@john."]);
+    }
+
+    #[test]
+    fn test_emails_require_a_dotted_domain() {
+        assert_eq!(emails("user@localhost @handle"), Vec::<String>::new());
+        assert_eq!(emails("end a@b.com."), vec!["a@b.com"]);
+        assert_eq!(
+            emails("user_1%x@sub-domain.example.org"),
+            vec!["user_1%x@sub-domain.example.org"]
+        );
+    }
+
+    // === Sort order ===
+    #[test]
+    fn test_sort_alphabetical_is_case_insensitive() {
+        let extractor = Extractor::builder()
+            .sort(SortOrder::Alphabetical)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#zebra #Apple #mango #banana @Zoe @adam");
+        assert_eq!(
+            result.hashtags,
+            vec!["#Apple", "#banana", "#mango", "#zebra"]
+        );
+        assert_eq!(result.mentions, vec!["@adam", "@Zoe"]);
+    }
+
+    #[test]
+    fn test_sort_alphabetical_breaks_ties_by_casing() {
+        let extractor = Extractor::builder()
+            .sort(SortOrder::Alphabetical)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#rust #Rust #RUST #a");
+        assert_eq!(result.hashtags, vec!["#a", "#RUST", "#Rust", "#rust"]);
+    }
+
+    #[test]
+    fn test_sort_appearance_is_default() {
+        let text = "#zebra #Apple";
+        let default = Extractor::builder().build().unwrap().parse(text);
+        let appearance = Extractor::builder()
+            .sort(SortOrder::Appearance)
+            .build()
+            .unwrap()
+            .parse(text);
+        assert_eq!(default.hashtags, vec!["#zebra", "#Apple"]);
+        assert_eq!(appearance.hashtags, default.hashtags);
+    }
+
+    // === Streaming ===
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_reader_dedups_across_lines() {
+        let text = "@a #x\n#x @b\n\n@a #y me@mail.com";
+        let mut tokens = Vec::new();
+        parse_reader(text.as_bytes(), true, true, |token| tokens.push(token)).unwrap();
+        let result = parse_mentions_hashtags(text, true, true).unwrap();
+        let mentions: Vec<&str> = tokens
+            .iter()
+            .filter(|t| matches!(t, Token::Mention(_)))
+            .map(Token::as_str)
+            .collect();
+        let hashtags: Vec<&str> = tokens
+            .iter()
+            .filter(|t| matches!(t, Token::Hashtag(_)))
+            .map(Token::as_str)
+            .collect();
+        assert_eq!(mentions, result.mentions);
+        assert_eq!(hashtags, result.hashtags);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_reader_respects_kind_flags() {
+        let mut tokens = Vec::new();
+        parse_reader("@a #x\n@b".as_bytes(), false, true, |token| {
+            tokens.push(token)
+        })
+        .unwrap();
+        assert_eq!(tokens, vec![Token::Hashtag("#x".to_string())]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_reader_reports_invalid_utf8() {
+        let bytes: &[u8] = b"#ok\n\xff\xfe\n";
+        let err = parse_reader(bytes, true, true, |_| {}).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_file_matches_parse_mentions_hashtags() {
+        let text = "@a #x\n#x @b me@mail.com\n";
+        let path = std::env::temp_dir().join("mentions_hashtags_parse_file.txt");
+        std::fs::write(&path, text).unwrap();
+        let result = parse_file(&path, true, false);
+        std::fs::remove_file(&path).unwrap();
+        let expected = parse_mentions_hashtags(text, true, false).unwrap();
+        let result = result.unwrap();
+        assert_eq!(result.mentions, expected.mentions);
+        assert!(result.hashtags.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_file_errors() {
+        let path = std::env::temp_dir().join("mentions_hashtags_parse_file_bad.txt");
+        std::fs::write(&path, b"#ok \xff").unwrap();
+        let err = parse_file(&path, true, true).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("offset 4"));
+
+        let missing = std::env::temp_dir().join("mentions_hashtags_no_such_file.txt");
+        let err = parse_file(missing, true, true).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    // === URLs ===
+    #[test]
+    fn test_ignore_in_urls_skips_fragments_and_paths() {
+        let extractor = Extractor::builder().ignore_in_urls(true).build().unwrap();
+        let result = extractor.parse("check https://x.com/a#b and #real");
+        assert_eq!(result.hashtags, vec!["#real"]);
+        let result = extractor.parse("see www.site.com/@bob or @alice, ftp://h/#x #y");
+        assert_eq!(result.mentions, vec!["@alice"]);
+        assert_eq!(result.hashtags, vec!["#y"]);
+    }
+
+    #[test]
+    fn test_ignore_in_urls_is_off_by_default() {
+        let result = Extractor::builder()
+            .build()
+            .unwrap()
+            .parse("check https://x.com/a#b and #real");
+        assert_eq!(result.hashtags, vec!["#b", "#real"]);
+    }
+
+    // === Locations ===
+    #[test]
+    fn test_parse_with_locations_lines_and_columns() {
+        let text = "@a #b\n\n  @c\n#d @a";
+        let locations = parse_with_locations(text);
+        let found: Vec<(&str, usize, usize)> = locations
+            .iter()
+            .map(|l| (l.text.as_str(), l.line, l.column))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("@a", 1, 1),
+                ("#b", 1, 4),
+                ("@c", 3, 3),
+                ("#d", 4, 1),
+                ("@a", 4, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_locations_counts_chars_and_slices_input() {
+        let text = "日本語 #日本\r\nçà @Zoë";
+        let locations = parse_with_locations(text);
+        assert_eq!((locations[0].line, locations[0].column), (1, 5));
+        assert_eq!((locations[1].line, locations[1].column), (2, 4));
+        for location in &locations {
+            assert_eq!(&text[location.byte_start..location.byte_end], location.text);
+        }
+    }
+
+    // === Denylist ===
+    #[test]
+    fn test_deny_is_case_insensitive_and_sigil_free() {
+        let deny: HashSet<String> = ["ad", "Sponsored", "fyp"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let extractor = Extractor::builder().deny(&deny).build().unwrap();
+        let result = extractor.parse("#AD #sponsored #FYP #recipe @ad @chef");
+        assert_eq!(result.hashtags, vec!["#recipe"]);
+        assert_eq!(result.mentions, vec!["@chef"]);
+    }
+
+    #[test]
+    fn test_deny_with_sigil_matches_one_kind() {
+        let extractor = Extractor::builder()
+            .deny(["#ad"])
+            .deny(["@spam"])
+            .build()
+            .unwrap();
+        let result = extractor.parse("#ad @ad #spam @spam");
+        assert_eq!(result.hashtags, vec!["#spam"]);
+        assert_eq!(result.mentions, vec!["@ad"]);
+    }
+
+    #[test]
+    fn test_deny_full_width_entries() {
+        let extractor = Extractor::builder()
+            .deny(["＃ad", "＠Spam"])
+            .build()
+            .unwrap();
+        let result = extractor.parse("#ad ＃AD #ok @spam ＠spam @me @ad");
+        assert_eq!(result.hashtags, vec!["#ok"]);
+        assert_eq!(result.mentions, vec!["@me", "@ad"]);
+    }
+
+    // === Allowlist ===
+    #[test]
+    fn test_allow_keeps_only_listed_tokens_in_order() {
+        let allow: HashSet<String> = ["launch", "Promo"].iter().map(|s| s.to_string()).collect();
+        let extractor = Extractor::builder().allow(&allow).build().unwrap();
+        let result = extractor.parse("#promo #other #LAUNCH @launch @bob #promo");
+        assert_eq!(result.hashtags, vec!["#promo", "#LAUNCH"]);
+        assert_eq!(result.mentions, vec!["@launch"]);
+    }
+
+    #[test]
+    fn test_allow_full_width_entries() {
+        let extractor = Extractor::builder()
+            .allow(["＃Sale", "＠brand"])
+            .build()
+            .unwrap();
+        let result = extractor.parse("#sale ＃SALE #other @brand ＠Brand @me");
+        assert_eq!(result.hashtags, vec!["#sale", "＃SALE"]);
+        assert_eq!(result.mentions, vec!["@brand", "＠Brand"]);
+    }
+
+    #[test]
+    fn test_empty_allowlist_returns_nothing() {
+        let extractor = Extractor::builder()
+            .allow(Vec::<String>::new())
+            .build()
+            .unwrap();
+        let result = extractor.parse("#a @b");
+        assert!(result.hashtags.is_empty());
+        assert!(result.mentions.is_empty());
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let extractor = Extractor::builder()
+            .allow(["a", "b"])
+            .deny(["#b"])
+            .build()
+            .unwrap();
+        let result = extractor.parse("#a #b #c @b");
+        assert_eq!(result.hashtags, vec!["#a"]);
+        assert_eq!(result.mentions, vec!["@b"]);
+    }
+
+    // === Presence ===
+    #[test]
+    fn test_has_mention() {
+        assert!(has_mention("@a"));
+        assert!(has_mention("me@mail.com and @b"));
+        assert!(!has_mention("me@mail.com"));
+        assert!(!has_mention(""));
+    }
+
+    #[test]
+    fn test_has_hashtag() {
+        assert!(has_hashtag("#日本"));
+        assert!(!has_hashtag("# not a tag"));
+        assert!(!has_hashtag("@only_mentions"));
+    }
+
+    // === Trim chars ===
+    #[test]
+    fn test_trim_chars_strips_both_ends() {
+        let extractor = Extractor::builder().trim_chars(&['_']).build().unwrap();
+        let result = extractor.parse("#__tag__ #tag @_bob_ #___ #mid_dle");
+        assert_eq!(result.hashtags, vec!["#tag", "#mid_dle"]);
+        assert_eq!(result.mentions, vec!["@bob"]);
+    }
+
+    #[test]
+    fn test_trim_chars_combines_with_trailing_punct() {
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .trim_chars(&['_'])
+            .build()
+            .unwrap();
+        assert_eq!(extractor.parse("#_go_crazy_.").hashtags, vec!["#go_crazy"]);
+        let default = Extractor::builder().build().unwrap();
+        assert_eq!(default.parse("#_go_crazy_.").hashtags, vec!["#_go_crazy_"]);
+    }
+
+    // === Stats ===
+    #[test]
+    fn test_parse_stats_matches_separate_calls() {
+        let text = "@a #x @b #x @a #y #x me@mail.com";
+        let stats = parse_stats(text);
+        let result = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(stats.mentions, result.mentions);
+        assert_eq!(stats.hashtags, result.hashtags);
+        assert_eq!(stats.mention_counts, count_mentions(text).unwrap());
+        assert_eq!(stats.hashtag_counts, count_hashtags(text).unwrap());
+    }
+
+    #[test]
+    fn test_parse_stats_empty() {
+        assert_eq!(parse_stats("no tokens"), ParseStats::default());
+    }
+
+    // === Lone and doubled sigils ===
+    #[test]
+    fn test_lone_sigils_yield_nothing() {
+        assert!(mentions("@").is_empty());
+        assert!(hashtags("# ").is_empty());
+        assert!(mentions("@ @ hello @").is_empty());
+        assert!(parse_tokens("@#").is_empty());
+    }
+
+    #[test]
+    fn test_doubled_sigils_keep_one() {
+        assert_eq!(mentions("@@user"), vec!["@user"]);
+        assert_eq!(hashtags("##tag"), vec!["#tag"]);
+        let result = Extractor::builder().build().unwrap().parse("@@user ##tag");
+        assert_eq!(result.mentions, vec!["@user"]);
+        assert_eq!(result.hashtags, vec!["#tag"]);
+    }
+
+    #[test]
+    fn test_punctuation_only_tokens() {
+        // A leading `_` is allowed, so these are matched by default.
+        assert_eq!(hashtags("#_ #_."), vec!["#_", "#_."]);
+        let extractor = Extractor::builder()
+            .require_alphanumeric(true)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#_ #_. @_._ #2025 #a-");
+        assert!(result.mentions.is_empty());
+        assert_eq!(result.hashtags, vec!["#2025", "#a"]);
+    }
+
+    // === From<&str> ===
+    #[test]
+    fn test_from_str_extracts_both() {
+        let text = "@charlidamelio @GucciOfficial #fyp #CapCut #fyp";
+        let mh = MentionsHashtags::from(text);
+        let expected = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(mh.mentions, expected.mentions);
+        assert_eq!(mh.hashtags, expected.hashtags);
+    }
+
+    #[test]
+    fn test_from_str_empty() {
+        let mh: MentionsHashtags = "".into();
+        assert!(mh.mentions.is_empty() && mh.hashtags.is_empty());
+    }
+
+    // === Ratios ===
+    #[test]
+    fn test_hashtag_char_ratio() {
+        assert_eq!(hashtag_char_ratio(""), 0.0);
+        assert_eq!(hashtag_char_ratio("no tags here"), 0.0);
+        assert_eq!(hashtag_char_ratio("#all"), 1.0);
+        assert_eq!(hashtag_char_ratio("#a #a"), 0.8);
+    }
+
+    #[test]
+    fn test_hashtag_char_ratio_counts_chars_not_bytes() {
+        // "#日本" is 3 chars (7 bytes); the whole text is 6 chars.
+        assert_eq!(hashtag_char_ratio("#日本 ab"), 0.5);
+    }
+
+    // === Fediverse ===
+    #[test]
+    fn test_fediverse_full_and_bare_forms() {
+        let text = "@a@instance.social @b @a@instance.social @c@sub.host.org.";
+        assert_eq!(
+            parse_fediverse_mentions(text),
+            vec!["@a@instance.social", "@b", "@c@sub.host.org"]
+        );
+    }
+
+    #[test]
+    fn test_fediverse_skips_emails_and_dotless_domains() {
+        assert_eq!(
+            parse_fediverse_mentions("a@b.com x@y @user@localhost"),
+            vec!["@user"]
+        );
+        let plain = "@MrBeast and @Дмитрий";
+        assert_eq!(parse_fediverse_mentions(plain), mentions(plain));
+    }
+
+    // === Canonical key ===
+    #[test]
+    fn test_canonical_key() {
+        assert_eq!(canonical_key("#MUSIC"), "#music");
+        assert_eq!(canonical_key("@AddisonRae"), "@addisonrae");
+        assert_eq!(canonical_key("＃ÉTÉ"), "#été");
+        assert_eq!(canonical_key(""), "");
+    }
+
+    #[test]
+    fn test_canonical_key_matches_internal_dedup() {
+        let text = "#Music ＃music #MUSIC #fyp";
+        let mut seen = HashSet::new();
+        let external: Vec<String> = hashtags(text)
+            .into_iter()
+            .filter(|t| seen.insert(canonical_key(t)))
+            .collect();
+        assert_eq!(external, parse_hashtags_dedup_ci(text).unwrap());
+        assert_eq!(external, vec!["#Music", "#fyp"]);
+    }
+
+    // === Context ===
+    #[test]
+    fn test_context_window_is_clamped() {
+        let found = parse_mentions_with_context("@a then @b", 3);
+        assert_eq!(
+            found,
+            vec![
+                ("@a".to_string(), "@a th".to_string()),
+                ("@b".to_string(), "en @b".to_string())
+            ]
+        );
+        let found = parse_mentions_with_context("hi @a", 100);
+        assert_eq!(found[0].1, "hi @a");
+        assert_eq!(parse_mentions_with_context("x @a y", 0)[0].1, "@a");
+    }
+
+    #[test]
+    fn test_context_respects_char_boundaries() {
+        let found = parse_mentions_with_context("日本語 @ユーザー 😀😀😀", 2);
+        assert_eq!(
+            found,
+            vec![("@ユーザー".to_string(), "語 @ユーザー 😀".to_string())]
+        );
+    }
+
+    // === Dedup after trim ===
+    #[test]
+    fn test_trimmed_dedup_with_punctuated_first() {
+        assert_eq!(parse_hashtags_trimmed("#fyp. #fyp").unwrap(), vec!["#fyp"]);
+        assert_eq!(
+            parse_mentions_trimmed("@bob. @alice @bob").unwrap(),
+            vec!["@bob", "@alice"]
+        );
+    }
+
+    #[test]
+    fn test_extractor_dedups_after_trim() {
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .case_insensitive_dedup(true)
+            .limit(2)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#fyp. #FYP #fyp- #viral. #viral #new");
+        assert_eq!(result.hashtags, vec!["#fyp", "#viral"]);
+    }
+
+    // === Split ===
+    #[test]
+    fn test_split_tags_matches_parse_and_strip() {
+        let text = "  @a hello #x\n@b  world #x me@mail.com #y ";
+        let (tags, rest) = split_tags(text);
+        let expected = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(tags.mentions, expected.mentions);
+        assert_eq!(tags.hashtags, expected.hashtags);
+        assert_eq!(rest, strip(text, true, true));
+        assert_eq!(rest, "hello world me@mail.com");
+    }
+
+    #[test]
+    fn test_split_tags_without_tokens() {
+        let (tags, rest) = split_tags("just  text");
+        assert!(tags.mentions.is_empty() && tags.hashtags.is_empty());
+        assert_eq!(rest, "just text");
+    }
+
+    // === Leading punctuation ===
+    #[test]
+    fn test_no_leading_hyphen_or_dot() {
+        assert!(hashtags("#-foo #.foo").is_empty());
+        assert!(mentions("@-foo @.foo").is_empty());
+        assert_eq!(
+            hashtags("#foo-bar #_foo #foo."),
+            vec!["#foo-bar", "#_foo", "#foo."]
+        );
+        assert!(parse_tokens("#- #...").is_empty());
+    }
+
+    #[test]
+    fn test_no_leading_hyphen_or_dot_with_charset() {
+        assert_eq!(
+            parse_hashtags_with_charset("#-foo #foo-bar #~x", "-~").unwrap(),
+            vec!["#foo-bar", "#~x"]
+        );
+        let extractor = Extractor::builder().build().unwrap();
+        assert_eq!(extractor.parse("#.foo #foo.bar").hashtags, vec!["#foo.bar"]);
+    }
+
+    // === Thread safety ===
+    #[test]
+    fn test_extractor_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Extractor>();
+        assert_send_sync::<ExtractorBuilder>();
+    }
+
+    #[test]
+    fn test_extractor_shared_across_threads() {
+        let extractor = Extractor::builder()
+            .case_insensitive_dedup(true)
+            .build()
+            .unwrap();
+        let extractor = &extractor;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    scope.spawn(move || {
+                        (0..200)
+                            .map(|j| extractor.parse(&format!("@user{i} #Tag{j} #tag{j}")))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            for (i, handle) in handles.into_iter().enumerate() {
+                for (j, result) in handle.join().unwrap().into_iter().enumerate() {
+                    assert_eq!(result.mentions, vec![format!("@user{i}")]);
+                    assert_eq!(result.hashtags, vec![format!("#Tag{j}")]);
+                }
+            }
+        });
+    }
+
+    // === Invisible characters ===
+    #[test]
+    fn test_strip_invisible_rejoins_split_tokens() {
+        let extractor = Extractor::builder().strip_invisible(true).build().unwrap();
+        let result = extractor.parse("#f\u{200B}yp @Mr\u{200D}Beast #\u{FEFF}viral #fyp");
+        assert_eq!(result.hashtags, vec!["#fyp", "#viral"]);
+        assert_eq!(result.mentions, vec!["@MrBeast"]);
+    }
+
+    #[test]
+    fn test_invisible_chars_split_tokens_by_default() {
+        let result = Extractor::builder()
+            .build()
+            .unwrap()
+            .parse("#f\u{200B}yp #a\u{200C}b");
+        assert_eq!(result.hashtags, vec!["#f", "#a"]);
+    }
+
+    // === Outcome ===
+    #[test]
+    fn test_outcome_distinguishes_blank_input() {
+        assert_eq!(parse_hashtags_outcome(""), ParseOutcome::Empty);
+        assert_eq!(parse_hashtags_outcome(" \n\t"), ParseOutcome::Empty);
+        assert_eq!(
+            parse_hashtags_outcome("plain"),
+            ParseOutcome::Matches(vec![])
+        );
+        assert_eq!(
+            parse_hashtags_outcome("#a #a #b"),
+            ParseOutcome::Matches(vec!["#a".to_string(), "#b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_outcome_into_vec() {
+        assert!(parse_mentions_outcome("").into_vec().is_empty());
+        assert_eq!(
+            parse_mentions_outcome("@x me@mail.com").into_vec(),
+            vec!["@x"]
+        );
+    }
+
+    // === Distinct characters ===
+    #[test]
+    fn test_min_distinct_chars() {
+        let extractor = Extractor::builder().min_distinct_chars(2).build().unwrap();
+        let result = extractor.parse("#aaaa #.... #ab #aA @zzz @ok");
+        assert_eq!(result.hashtags, vec!["#ab", "#aA"]);
+        assert_eq!(result.mentions, vec!["@ok"]);
+    }
+
+    #[test]
+    fn test_min_distinct_chars_off_by_default_and_reported() {
+        let result = Extractor::builder().build().unwrap().parse("#aaaa");
+        assert_eq!(result.hashtags, vec!["#aaaa"]);
+
+        let extractor = Extractor::builder().min_distinct_chars(3).build().unwrap();
+        let (accepted, rejected) = extractor.validate("#abc #abab");
+        assert_eq!(accepted.hashtags, vec!["#abc"]);
+        assert_eq!(rejected[0].token, "#abab");
+        assert_eq!(rejected[0].reason, RejectReason::TooRepetitive);
+    }
+
+    // === Leading digits ===
+    #[test]
+    fn test_no_leading_digit() {
+        let extractor = Extractor::builder().no_leading_digit(true).build().unwrap();
+        let result = extractor.parse("#2025 #1direction #Challenge2025 #q2 #２０２５ @2pac @tupac");
+        assert_eq!(result.hashtags, vec!["#Challenge2025", "#q2"]);
+        assert_eq!(result.mentions, vec!["@tupac"]);
+    }
+
+    #[test]
+    fn test_leading_digit_allowed_by_default() {
+        let result = Extractor::builder()
+            .build()
+            .unwrap()
+            .parse("#2025 #1direction #Challenge2025");
+        assert_eq!(
+            result.hashtags,
+            vec!["#2025", "#1direction", "#Challenge2025"]
+        );
+    }
+
+    // === Tokio ===
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_parse_spawn_blocking_matches_sync_parse() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let captions: Vec<String> = (0..20).map(|i| format!("@u{i} #t{i} #t{i}")).collect();
+        for caption in captions {
+            let expected = parse_mentions_hashtags(&caption, true, true).unwrap();
+            let result = runtime.block_on(parse_spawn_blocking(caption));
+            assert_eq!(result.mentions, expected.mentions);
+            assert_eq!(result.hashtags, expected.hashtags);
+        }
+    }
+
+    // === Merge ===
+    #[test]
+    fn test_merge_overlapping_results() {
+        let mut merged = parse_mentions_hashtags("@a @b #x #y", true, true).unwrap();
+        merged.merge(parse_mentions_hashtags("@b @c #y #Y #z", true, true).unwrap());
+        assert_eq!(merged.mentions, vec!["@a", "@b", "@c"]);
+        assert_eq!(merged.hashtags, vec!["#x", "#y", "#Y", "#z"]);
+    }
+
+    #[test]
+    fn test_merge_dedup_ci() {
+        let mut merged = MentionsHashtags::from("@Alice #Rust");
+        merged.merge_dedup_ci(MentionsHashtags::from("@alice @bob #RUST #rust #go"));
+        assert_eq!(merged.mentions, vec!["@Alice", "@bob"]);
+        assert_eq!(merged.hashtags, vec!["#Rust", "#go"]);
+    }
+
+    // === Boundaries ===
+    #[test]
+    fn test_require_boundary() {
+        let extractor = Extractor::builder().require_boundary(true).build().unwrap();
+        assert!(extractor.parse("foo#bar").hashtags.is_empty());
+        assert_eq!(extractor.parse("foo #bar").hashtags, vec!["#bar"]);
+        assert_eq!(extractor.parse("(#bar)").hashtags, vec!["#bar"]);
+        assert_eq!(
+            extractor.parse("#start [@a] {#b}").hashtags,
+            vec!["#start", "#b"]
+        );
+        assert_eq!(extractor.parse("[@a] x/@b \"@c").mentions, vec!["@a"]);
+    }
+
+    #[test]
+    fn test_boundary_not_required_by_default() {
+        let result = Extractor::builder().build().unwrap().parse("foo#bar x/@b");
+        assert_eq!(result.hashtags, vec!["#bar"]);
+        assert_eq!(result.mentions, vec!["@b"]);
+    }
+
+    // === Prelude ===
+    #[test]
+    fn test_prelude_exports_main_items() {
+        use crate::prelude;
+        let result: prelude::MentionsHashtags =
+            prelude::parse_mentions_hashtags("@a #b", true, true).unwrap();
+        assert_eq!(result.mentions, prelude::mentions("@a"));
+        assert_eq!(result.hashtags, prelude::hashtags("#b"));
+        let extractor: prelude::Extractor = prelude::ExtractorBuilder::new().build().unwrap();
+        assert_eq!(extractor.parse("#b").hashtags, vec!["#b"]);
+    }
+
+    // === Rich tokens ===
+    #[test]
+    fn test_parse_mentions_rich_pairs_raw_and_bare() {
+        let text = "@MrBeast @Khaby.Lame @MrBeast ＠ユーザー";
+        let found = parse_mentions_rich(text);
+        let raw: Vec<&str> = found.iter().map(|m| m.raw.as_str()).collect();
+        let bare: Vec<&str> = found.iter().map(|m| m.handle.as_str()).collect();
+        assert_eq!(raw, mentions(text));
+        assert_eq!(bare, parse_mentions_bare(text).unwrap());
+        assert_eq!(bare, vec!["MrBeast", "Khaby.Lame", "ユーザー"]);
+    }
+
+    #[test]
+    fn test_parse_hashtags_rich_pairs_raw_and_bare() {
+        let found = parse_hashtags_rich("#fyp #日本 #fyp");
+        assert_eq!(
+            found,
+            vec![
+                Hashtag {
+                    raw: "#fyp".to_string(),
+                    tag: "fyp".to_string()
+                },
+                Hashtag {
+                    raw: "#日本".to_string(),
+                    tag: "日本".to_string()
+                },
+            ]
+        );
     }
 
+    // === Scan budget ===
     #[test]
-    fn test_parse_none_enabled() {
-        let result = parse_mentions_hashtags("@Khaby.Lame #viral", false, false).unwrap();
+    fn test_max_scan_bytes_drops_straddling_token() {
+        let extractor = Extractor::builder().max_scan_bytes(10).build().unwrap();
+        // The cut falls inside `#second`, which is discarded.
+        let result = extractor.parse("#first #second #third");
+        assert_eq!(result.hashtags, vec!["#first"]);
+        // A token ending exactly at the cut that continues past it is dropped too.
+        assert!(extractor.parse("@abcdefghij").mentions.is_empty());
+        // Input within the budget is untouched.
+        assert_eq!(extractor.parse("#a #b").hashtags, vec!["#a", "#b"]);
+    }
+
+    #[test]
+    fn test_max_scan_bytes_keeps_token_ending_at_cut() {
+        let extractor = Extractor::builder().max_scan_bytes(8).build().unwrap();
+        assert_eq!(
+            extractor.parse("#abc #de xyz").hashtags,
+            vec!["#abc", "#de"]
+        );
+        assert_eq!(
+            extractor.parse("#abc #de, @x").hashtags,
+            vec!["#abc", "#de"]
+        );
+        assert_eq!(extractor.parse("#abc #def").hashtags, vec!["#abc"]);
+        // A custom charset decides what continues a token.
+        let underscores = Extractor::builder()
+            .hashtag_chars("")
+            .max_scan_bytes(8)
+            .build()
+            .unwrap();
+        assert_eq!(
+            underscores.parse("#abc #de_x").hashtags,
+            vec!["#abc", "#de"]
+        );
+    }
+
+    #[test]
+    fn test_max_scan_bytes_is_char_safe() {
+        let extractor = Extractor::builder().max_scan_bytes(8).build().unwrap();
+        // Byte 8 falls inside `本` (bytes 7..10); the cut moves back to byte 7 and the
+        // partial `#日` is dropped.
+        let result = extractor.parse("#a #日本 #c");
+        assert_eq!(result.hashtags, vec!["#a"]);
+        let huge = format!("#keep {}", "x".repeat(1 << 20));
+        let result = Extractor::builder()
+            .max_scan_bytes(64)
+            .build()
+            .unwrap()
+            .parse(&huge);
+        assert_eq!(result.hashtags, vec!["#keep"]);
+    }
+
+    // === Validation ===
+    #[test]
+    fn test_validate_reports_reasons() {
+        let extractor = Extractor::builder()
+            .min_len(2)
+            .max_len(8)
+            .no_leading_digit(true)
+            .dots(DotPolicy::Reject)
+            .deny(["spam"])
+            .build()
+            .unwrap();
+        let (accepted, rejected) =
+            extractor.validate("#ok #a #2025 #verylongtag @foo..bar #spam #ok #a @fine");
+        assert_eq!(accepted.hashtags, vec!["#ok"]);
+        assert_eq!(accepted.mentions, vec!["@fine"]);
+        let reasons: Vec<(&str, RejectReason)> = rejected
+            .iter()
+            .map(|r| (r.token.as_str(), r.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("#a", RejectReason::TooShort),
+                ("#2025", RejectReason::LeadingDigit),
+                ("#verylongtag", RejectReason::TooLong),
+                ("@foo..bar", RejectReason::BadDots),
+                ("#spam", RejectReason::Denied),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_matches_parse() {
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .require_alpha(true)
+            .allow(["x", "y", "2"])
+            .build()
+            .unwrap();
+        let text = "#x. #2 #z @y me@mail.com";
+        let (accepted, rejected) = extractor.validate(text);
+        let parsed = extractor.parse(text);
+        assert_eq!(accepted.mentions, parsed.mentions);
+        assert_eq!(accepted.hashtags, parsed.hashtags);
+        assert_eq!(rejected[0].reason, RejectReason::NoLetter);
+        assert_eq!(rejected[1].reason, RejectReason::NotAllowed);
+        assert_eq!(RejectReason::NotAllowed.to_string(), "not in the allowlist");
+    }
+
+    // === FFI ===
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_round_trip() {
+        use super::ffi::*;
+        use std::ffi::{CStr, CString};
+
+        let input = CString::new("@a @b @a #fyp #café").unwrap();
+        unsafe {
+            let out = mh_parse_mentions(input.as_ptr());
+            assert_eq!(CStr::from_ptr(out).to_str().unwrap(), "@a\n@b\n");
+            mh_string_free(out);
+
+            let out = mh_parse_hashtags(input.as_ptr());
+            assert_eq!(CStr::from_ptr(out).to_str().unwrap(), "#fyp\n#café\n");
+            mh_string_free(out);
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_null_and_invalid_input() {
+        use super::ffi::*;
+        use std::ffi::{CStr, CString};
+
+        let invalid = CString::new(vec![b'#', 0xff]).unwrap();
+        let empty = CString::new("no tokens").unwrap();
+        unsafe {
+            assert!(mh_parse_mentions(std::ptr::null()).is_null());
+            assert!(mh_parse_hashtags(invalid.as_ptr()).is_null());
+
+            let out = mh_parse_hashtags(empty.as_ptr());
+            assert_eq!(CStr::from_ptr(out).to_bytes(), b"");
+            mh_string_free(out);
+            mh_string_free(std::ptr::null_mut());
+        }
+    }
+
+    // === Repeat Collapsing ===
+    #[test]
+    fn test_collapse_repeats_shortens_long_runs() {
+        let extractor = Extractor::builder()
+            .collapse_repeats(Some(2))
+            .build()
+            .unwrap();
+        let result = extractor.parse("#looooooooool #loool #lool #yesssss @aaaaaaron #book");
+        assert_eq!(result.hashtags, vec!["#lool", "#yess", "#book"]);
+        assert_eq!(result.mentions, vec!["@aaron"]);
+    }
+
+    #[test]
+    fn test_collapse_repeats_exact_chars_and_off_by_default() {
+        let extractor = Extractor::builder()
+            .collapse_repeats(Some(1))
+            .build()
+            .unwrap();
+        assert_eq!(
+            extractor.parse("#LLlllOOoo #ŝŝŝŝ").hashtags,
+            vec!["#LlOo", "#ŝ"]
+        );
+        assert_eq!(
+            Extractor::builder()
+                .collapse_repeats(Some(0))
+                .build()
+                .unwrap()
+                .parse("#zzz")
+                .hashtags,
+            vec!["#z"]
+        );
+        assert_eq!(hashtags("#yesssss"), vec!["#yesssss"]);
+    }
+
+    #[test]
+    fn test_collapse_repeats_before_length_check() {
+        let extractor = Extractor::builder()
+            .collapse_repeats(Some(2))
+            .max_len(5)
+            .build()
+            .unwrap();
+        assert_eq!(extractor.parse("#nooooooooo").hashtags, vec!["#noo"]);
+    }
+
+    // === Grouping ===
+    #[test]
+    fn test_group_hashtags_by_initial() {
+        let groups =
+            group_hashtags_by_initial("#fyp #CapCut #Fun #fyp #Émoji #énorme #日本 #42 #_x");
+        let keys: Vec<char> = groups.keys().copied().collect();
+        assert_eq!(keys, vec!['#', 'c', 'f', 'é', '日']);
+        assert_eq!(groups[&'f'], vec!["#fyp", "#Fun"]);
+        assert_eq!(groups[&'é'], vec!["#Émoji", "#énorme"]);
+        assert_eq!(groups[&NON_LETTER_INITIAL], vec!["#42", "#_x"]);
+    }
+
+    #[test]
+    fn test_group_hashtags_by_initial_empty() {
+        assert!(group_hashtags_by_initial("@only mentions").is_empty());
+    }
+
+    // === HTML Input ===
+    #[test]
+    fn test_input_is_html_skips_attributes() {
+        let extractor = Extractor::builder().input_is_html(true).build().unwrap();
+        let html = r##"<a href="#anchor" title='a > #b'>#real</a> <img alt="#alt"/>"##;
+        assert_eq!(extractor.parse(html).hashtags, vec!["#real"]);
+        assert_eq!(hashtags(html), vec!["#anchor", "#b", "#real", "#alt"]);
+    }
+
+    #[test]
+    fn test_input_is_html_text_and_entities() {
+        let extractor = Extractor::builder().input_is_html(true).build().unwrap();
+        let html = "<p>it&#39;s <b>@a</b><i>@b</i></p><p>#x</p><p>y</p> \
+                    <!-- #hidden --> a < b &amp; &#35;decoded &bogus; #end";
+        let result = extractor.parse(html);
+        assert_eq!(result.mentions, vec!["@a", "@b"]);
+        assert_eq!(result.hashtags, vec!["#x", "#decoded", "#end"]);
+    }
+
+    #[test]
+    fn test_input_is_html_unterminated_tag() {
+        let extractor = Extractor::builder().input_is_html(true).build().unwrap();
+        assert_eq!(extractor.parse("#ok <a href=\"#no").hashtags, vec!["#ok"]);
+        assert_eq!(extractor.parse("#a & #b").hashtags, vec!["#a", "#b"]);
+    }
+
+    // === Line Output ===
+    #[test]
+    fn test_to_lines_format() {
+        assert_eq!(mentions_to_lines("@a #x @b @a"), "@a\n@b\n");
+        assert_eq!(hashtags_to_lines("@a #x #y #x"), "#x\n#y\n");
+        assert_eq!(
+            hashtags_to_lines("#x #y").lines().collect::<Vec<_>>(),
+            hashtags("#x #y")
+        );
+    }
+
+    #[test]
+    fn test_to_lines_empty() {
+        assert_eq!(mentions_to_lines(""), "");
+        assert_eq!(hashtags_to_lines("plain text"), "");
+    }
+
+    // === Optional Dedup ===
+    #[test]
+    fn test_dedup_disabled_keeps_every_match() {
+        let extractor = Extractor::builder().dedup(false).build().unwrap();
+        let result = extractor.parse("#fyp @a #fyp #viral @a #FYP");
+        assert_eq!(result.hashtags, vec!["#fyp", "#fyp", "#viral", "#FYP"]);
+        assert_eq!(result.mentions, vec!["@a", "@a"]);
+        assert_eq!(
+            result.hashtags.len(),
+            count_hashtags("#fyp @a #fyp #viral @a #FYP")
+                .unwrap()
+                .values()
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_dedup_disabled_with_limit_and_case() {
+        let extractor = Extractor::builder()
+            .dedup(false)
+            .normalize_case(true)
+            .case_insensitive_dedup(true)
+            .limit(3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            extractor.parse("#A #a #b #c").hashtags,
+            vec!["#a", "#a", "#b"]
+        );
+    }
+
+    // === Mention Relations ===
+    #[test]
+    fn test_relations_keywords() {
+        let found = parse_mentions_with_relations("RT @a: nice. Via @b, CC:@c cc: @d @e covia @f");
+        assert_eq!(
+            found,
+            vec![
+                ("@a".to_string(), Some(Relation::Retweet)),
+                ("@b".to_string(), Some(Relation::Via)),
+                ("@c".to_string(), Some(Relation::Cc)),
+                ("@d".to_string(), Some(Relation::Cc)),
+                ("@e".to_string(), None),
+                ("@f".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relations_dedup_and_plain_mentions() {
+        let found = parse_mentions_with_relations("@a said hi, via @a and rt @a");
+        assert_eq!(found, vec![("@a".to_string(), Some(Relation::Via))]);
+        let text = "@x @y me@mail.com #tag";
+        let names: Vec<String> = parse_mentions_with_relations(text)
+            .into_iter()
+            .map(|(m, _)| m)
+            .collect();
+        assert_eq!(names, mentions(text));
+    }
+
+    // === Unicode Normalization ===
+    #[test]
+    fn test_combining_marks_stay_in_token() {
+        assert_eq!(
+            hashtags("#cafe\u{301}! #nai\u{308}ve"),
+            vec!["#cafe\u{301}", "#nai\u{308}ve"]
+        );
+        assert_eq!(hashtags("#cafe\u{301} #caf\u{e9}").len(), 2);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_normalize_nfc_merges_encodings() {
+        let extractor = Extractor::builder().normalize_nfc(true).build().unwrap();
+        let result = extractor.parse("#cafe\u{301} #caf\u{e9} @jose\u{301} #plain");
+        assert_eq!(result.hashtags, vec!["#caf\u{e9}", "#plain"]);
+        assert_eq!(result.mentions, vec!["@jos\u{e9}"]);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_normalize_nfc_with_case_insensitive_dedup() {
+        let extractor = Extractor::builder()
+            .normalize_nfc(true)
+            .case_insensitive_dedup(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            extractor.parse("#CAFE\u{301} #caf\u{e9}").hashtags,
+            vec!["#CAF\u{c9}"]
+        );
+    }
+
+    // === Borrowed Results ===
+    #[test]
+    fn test_parse_cow_borrows_unchanged_tokens() {
+        use std::borrow::Cow;
+
+        let text = "@a #fyp #Viral. #fyp";
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .build()
+            .unwrap();
+        let result = extractor.parse_cow(text);
+        assert_eq!(result.hashtags, vec!["#fyp", "#Viral"]);
+        for token in result.mentions.iter().chain(&result.hashtags) {
+            assert!(matches!(token, Cow::Borrowed(_)), "{token} should borrow");
+        }
+        let owned = result.clone().into_owned();
+        assert_eq!(owned.hashtags, extractor.parse(text).hashtags);
+    }
+
+    #[test]
+    fn test_parse_cow_owns_rewritten_tokens() {
+        use std::borrow::Cow;
+
+        let extractor = Extractor::builder().normalize_case(true).build().unwrap();
+        let result = extractor.parse_cow("#fyp #Viral #VIRAL");
+        assert!(matches!(result.hashtags[0], Cow::Borrowed("#fyp")));
+        assert!(matches!(result.hashtags[1], Cow::Owned(ref tag) if tag == "#viral"));
+        assert_eq!(result.hashtags.len(), 2);
+
+        let extractor = Extractor::builder().strip_invisible(true).build().unwrap();
+        let result = extractor.parse_cow("#f\u{200B}yp #ok");
+        assert_eq!(result.hashtags, vec!["#fyp", "#ok"]);
+        assert!(result.hashtags.iter().all(|t| matches!(t, Cow::Owned(_))));
+        assert!(matches!(
+            extractor.parse_cow("#ok").hashtags[0],
+            Cow::Borrowed(_)
+        ));
+    }
+
+    // === Visitor ===
+    #[test]
+    fn test_visit_tokens_reports_every_match() {
+        let text = "@a #x me@mail.com #x @b";
+        let mut seen = Vec::new();
+        visit_tokens(text, |kind, token, start, end| {
+            assert_eq!(&text[start..end], token);
+            seen.push((kind, token));
+        });
+        assert_eq!(
+            seen,
+            vec![
+                (TokenKind::Mention, "@a"),
+                (TokenKind::Hashtag, "#x"),
+                (TokenKind::Hashtag, "#x"),
+                (TokenKind::Mention, "@b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visit_tokens_agrees_with_parse_tokens() {
+        let text = "＃全角 #fyp @MrBeast, @MrBeast";
+        let mut visited = Vec::new();
+        visit_tokens(text, |kind, token, start, end| {
+            visited.push((kind, token.to_string(), start, end))
+        });
+        let parsed: Vec<_> = parse_tokens(text)
+            .into_iter()
+            .map(|(token, start, end)| (token.kind(), token.as_str().to_string(), start, end))
+            .collect();
+        assert_eq!(visited, parsed);
+    }
+
+    // === Replacement Characters ===
+    #[test]
+    fn test_replacement_char_terminates_token() {
+        assert_eq!(hashtags("#fy\u{FFFD}p"), vec!["#fy"]);
+        assert_eq!(mentions("@jo\u{FFFD}e"), vec!["@jo"]);
+        assert!(hashtags("#\u{FFFD}fyp").is_empty());
+        let lossy = String::from_utf8_lossy(b"#caf\xC3 ok");
+        assert_eq!(hashtags(&lossy), vec!["#caf"]);
+    }
+
+    #[test]
+    fn test_replacement_char_in_extractor_and_charset() {
+        let extractor = Extractor::builder().build().unwrap();
+        assert_eq!(extractor.parse("#a\u{FFFD}b").hashtags, vec!["#a"]);
+        assert_eq!(
+            parse_hashtags_with_charset("#a\u{FFFD}b", "_").unwrap(),
+            vec!["#a"]
+        );
+    }
+
+    // === Trailing Hashtag Block ===
+    #[test]
+    fn test_trailing_hashtags_ignores_inline() {
+        let caption = "Loving this #mid spot!\n\n#fyp #viral\t#foryou #fyp  \n";
+        assert_eq!(
+            trailing_hashtags(caption),
+            vec!["#fyp", "#viral", "#foryou"]
+        );
+        assert_eq!(trailing_hashtags("#a #b"), vec!["#a", "#b"]);
+        assert_eq!(trailing_hashtags("text #a#b"), vec!["#a", "#b"]);
+    }
+
+    #[test]
+    fn test_trailing_hashtags_block_interrupted() {
+        assert_eq!(trailing_hashtags("#a @me #b #c"), vec!["#b", "#c"]);
+        assert!(trailing_hashtags("#fyp #viral 🔥").is_empty());
+        assert!(trailing_hashtags("#inline only, then words").is_empty());
+        assert!(trailing_hashtags("   ").is_empty());
+    }
+
+    #[test]
+    fn test_trailing_hashtags_block_needs_whitespace_before() {
+        assert_eq!(trailing_hashtags("word#a #b"), vec!["#b"]);
+        assert_eq!(trailing_hashtags("word#a#b #c"), vec!["#c"]);
+        assert!(trailing_hashtags("word#a").is_empty());
+        assert!(trailing_hashtags("see:#a").is_empty());
+        assert_eq!(trailing_hashtags("#a#b"), vec!["#a", "#b"]);
+    }
+
+    // === Unique Token Count ===
+    #[test]
+    fn test_unique_token_count() {
+        let text = "@foo #foo #fyp #fyp @foo @Foo me@mail.com";
+        let parsed = parse_mentions_hashtags(text, true, true).unwrap();
+        assert_eq!(unique_token_count(text), 4);
+        assert_eq!(
+            unique_token_count(text),
+            parsed.mentions.len() + parsed.hashtags.len()
+        );
+        assert_eq!(unique_token_count(""), 0);
+    }
+
+    // === Custom Regex ===
+    #[test]
+    fn test_parse_with_regex_dedups_in_order() {
+        let pattern = regex::Regex::new(r"![a-z]+").unwrap();
+        assert_eq!(
+            parse_with_regex("!b !a !b x!c", &pattern),
+            vec!["!b", "!a", "!c"]
+        );
+        assert!(parse_with_regex("", &pattern).is_empty());
+    }
+
+    #[test]
+    fn test_parse_with_regex_builtin_pattern() {
+        let text = "@a me@mail.com @a";
+        assert_eq!(parse_with_regex(text, hashtag_regex()), hashtags(text));
+        assert_eq!(
+            parse_with_regex(text, mention_regex()),
+            vec!["@a", "@mail.com"]
+        );
+    }
+
+    // === Soft Hyphens ===
+    #[test]
+    fn test_strip_invisible_removes_soft_hyphen() {
+        let extractor = Extractor::builder().strip_invisible(true).build().unwrap();
+        let result = extractor.parse("#foo\u{AD}bar @jane\u{AD}doe #ok\u{AD}");
+        assert_eq!(result.hashtags, vec!["#foobar", "#ok"]);
+        assert_eq!(result.mentions, vec!["@janedoe"]);
+        assert_eq!(hashtags("#foo\u{AD}bar"), vec!["#foo"]);
+    }
+
+    // === Result Size ===
+    #[test]
+    fn test_mentions_hashtags_is_empty_and_len() {
+        let empty = MentionsHashtags::default();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+
+        let only_mentions = parse_mentions_hashtags("@a @b #x", true, false).unwrap();
+        assert!(!only_mentions.is_empty());
+        assert_eq!(only_mentions.len(), 2);
+
+        let both = MentionsHashtags::from("@a #x #y #x");
+        assert_eq!(both.len(), 3);
+    }
+
+    // === Line Index ===
+    #[test]
+    fn test_parse_hashtags_by_line() {
+        let lines = ["#a #b #a", "", "#b", "#A #a"];
+        let index = parse_hashtags_by_line(&lines);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index["#a"], vec![0, 3]);
+        assert_eq!(index["#b"], vec![0, 2]);
+        assert_eq!(index["#A"], vec![3]);
+    }
+
+    #[test]
+    fn test_parse_hashtags_by_line_empty() {
+        assert!(parse_hashtags_by_line(&[]).is_empty());
+        assert!(parse_hashtags_by_line(&["@only", "text"]).is_empty());
+    }
+
+    // === Primary Hashtag ===
+    #[test]
+    fn test_primary_hashtag_strategies() {
+        let text = "#b #aa #cc #b #cc";
+        assert_eq!(
+            primary_hashtag(text, PrimaryStrategy::First).as_deref(),
+            Some("#b")
+        );
+        assert_eq!(
+            primary_hashtag(text, PrimaryStrategy::MostFrequent).as_deref(),
+            Some("#b")
+        );
+        assert_eq!(
+            primary_hashtag(text, PrimaryStrategy::Longest).as_deref(),
+            Some("#aa")
+        );
+        assert_eq!(
+            primary_hashtag("#ab #日本語", PrimaryStrategy::Longest).as_deref(),
+            Some("#日本語")
+        );
+    }
+
+    #[test]
+    fn test_primary_hashtag_none_without_tags() {
+        for strategy in [
+            PrimaryStrategy::First,
+            PrimaryStrategy::MostFrequent,
+            PrimaryStrategy::Longest,
+        ] {
+            assert_eq!(primary_hashtag("@a text", strategy), None);
+        }
+    }
+
+    // === ASCII Folding ===
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_fold_ascii_dedups_accented_forms() {
+        let extractor = Extractor::builder().fold_ascii(true).build().unwrap();
+        let result = extractor.parse("#café #cafe #cafe\u{301} #Cafe @zoë @zoe #naïve #naive");
+        assert_eq!(result.hashtags, vec!["#café", "#Cafe", "#naïve"]);
+        assert_eq!(result.mentions, vec!["@zoë"]);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_fold_ascii_maps_letters_without_decomposition() {
+        let extractor = Extractor::builder().fold_ascii(true).build().unwrap();
+        let result = extractor.parse(
+            "#straße #strasse #łódź #lodz #æble #aeble #Þór #THor #orsted #ørsted #東京 #東京",
+        );
+        assert_eq!(
+            result.hashtags,
+            vec!["#straße", "#łódź", "#æble", "#Þór", "#orsted", "#東京"]
+        );
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_fold_ascii_with_case_insensitive_dedup() {
+        let extractor = Extractor::builder()
+            .fold_ascii(true)
+            .case_insensitive_dedup(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            extractor
+                .parse("#Éclair #eclair #ECLAIR #ørsted #orsted")
+                .hashtags,
+            vec!["#Éclair", "#ørsted"]
+        );
+        let plain = Extractor::builder().build().unwrap();
+        assert_eq!(plain.parse("#café #cafe").hashtags.len(), 2);
+    }
+
+    // === Char Spans ===
+    #[test]
+    fn test_parse_tokens_char_spans_multibyte() {
+        let text = "日本 #東京 @josé, #fyp";
+        let bytes = parse_tokens(text);
+        let chars = parse_tokens_char_spans(text);
+        assert_eq!(bytes.len(), chars.len());
+        for ((byte_token, byte_start, byte_end), (char_token, char_start, char_end)) in
+            bytes.iter().zip(&chars)
+        {
+            assert_eq!(byte_token, char_token);
+            assert_eq!(text[..*byte_start].chars().count(), *char_start);
+            assert_eq!(text[..*byte_end].chars().count(), *char_end);
+        }
+        assert_eq!(chars[0].1, 3);
+        assert_eq!(chars[1].1..chars[1].2, 7..12);
+    }
+
+    #[test]
+    fn test_parse_tokens_char_spans_ascii_matches_bytes() {
+        let text = "@a #x @b";
+        assert_eq!(parse_tokens_char_spans(text), parse_tokens(text));
+        assert!(parse_tokens_char_spans("").is_empty());
+    }
+
+    // === Sanitize ===
+    #[test]
+    fn test_sanitize_hashtags_applies_fixes() {
+        let text = "Go #Te\u{AD}am! #win. #win #100 #_ www.x.com/#frag @me #ok-";
+        assert_eq!(sanitize_hashtags(text), vec!["#Team", "#win", "#ok"]);
+        assert!(sanitize_hashtags("").is_empty());
+        let long = format!("#{}", "a".repeat(MAX_TOKEN_LEN + 10));
+        assert_eq!(sanitize_hashtags(&format!("{long} #ok")), vec!["#ok"]);
+    }
+
+    #[test]
+    fn test_sanitize_hashtags_matches_equivalent_extractor() {
+        let text = "#a. #b\u{200B}c #1 http://h.com/p#x #a";
+        let extractor = Extractor::builder()
+            .mentions(false)
+            .strip_invisible(true)
+            .ignore_in_urls(true)
+            .trim_trailing_punct(true)
+            .require_alpha(true)
+            .max_len(MAX_TOKEN_LEN)
+            .build()
+            .unwrap();
+        assert_eq!(sanitize_hashtags(text), extractor.parse(text).hashtags);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_sanitize_hashtags_normalizes_nfc() {
+        assert_eq!(
+            sanitize_hashtags("#cafe\u{301} #caf\u{e9}"),
+            vec!["#caf\u{e9}"]
+        );
+    }
+
+    // === Byte Input ===
+    #[test]
+    fn test_parse_mentions_bytes_matches_str_path() {
+        let text = "@a me@mail.com @b.c-d @a ＠wide @Дмитрий x_@no é@no";
+        let from_bytes: Vec<String> = parse_mentions_bytes(text.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|m| String::from_utf8(m).unwrap())
+            .collect();
+        assert_eq!(from_bytes, parse_mentions(text).unwrap());
+    }
+
+    #[test]
+    fn test_parse_mentions_bytes_invalid_utf8() {
+        let found = parse_mentions_bytes(b"@jos\xE9 \xE9@ok \x80\x80@also @").unwrap();
+        assert_eq!(
+            found,
+            vec![b"@jos".to_vec(), b"@ok".to_vec(), b"@also".to_vec()]
+        );
+        assert!(parse_mentions_bytes(b"").unwrap().is_empty());
+    }
+
+    // === Range Parsing ===
+    #[test]
+    fn test_parse_mentions_in_range_overlap() {
+        let doc = "@alice hi\n@bob, @carol me@mail.com @dave";
+        let all: Vec<_> = parse_mentions_in_range(doc, 0..doc.len());
+        assert_eq!(all.len(), 4);
+        assert_eq!(parse_mentions_in_range(doc, 3..12).len(), 2);
+        assert_eq!(
+            parse_mentions_in_range(doc, 12..13),
+            vec![("@bob".to_string(), 10, 14)]
+        );
+        for (mention, start, end) in &all {
+            assert_eq!(&doc[*start..*end], mention);
+        }
+        assert!(parse_mentions_in_range(doc, 6..9).is_empty());
+    }
+
+    #[test]
+    fn test_parse_mentions_in_range_edges() {
+        let doc = "x@no é@no @ünï @end";
+        assert!(parse_mentions_in_range(doc, 0..9).is_empty());
+        assert_eq!(parse_mentions_in_range(doc, 13..14)[0].0, "@ünï");
+        assert_eq!(parse_mentions_in_range(doc, 100..200), vec![]);
+        assert_eq!(
+            parse_mentions_in_range(doc, 20..20),
+            vec![("@end".to_string(), 18, 22)]
+        );
+        assert!(parse_mentions_in_range(doc, 18..18).is_empty());
+    }
+
+    // === Concatenated Hashtags ===
+    #[test]
+    fn test_concatenated_hashtags_split_at_each_sigil() {
+        assert_eq!(
+            hashtags("#fyp#viral#foryou"),
+            vec!["#fyp", "#viral", "#foryou"]
+        );
+        assert_eq!(hashtags("look:#a#b＃c##d#"), vec!["#a", "#b", "＃c", "#d"]);
+        assert_eq!(
+            parse_tokens("#a#b")
+                .into_iter()
+                .map(|(_, start, end)| (start, end))
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (2, 4)]
+        );
+    }
+
+    #[test]
+    fn test_concatenated_hashtags_in_extractor() {
+        let extractor = Extractor::builder()
+            .trim_trailing_punct(true)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#fyp#viral.#fyp#foryou@user");
+        assert_eq!(result.hashtags, vec!["#fyp", "#viral", "#foryou"]);
         assert!(result.mentions.is_empty());
-        assert!(result.hashtags.is_empty());
+        let bounded = Extractor::builder().require_boundary(true).build().unwrap();
+        assert_eq!(bounded.parse("#fyp#viral").hashtags, vec!["#fyp"]);
+    }
+
+    // === Underscore-Only Tokens ===
+    #[test]
+    fn test_underscore_only_tokens_kept_by_default() {
+        assert_eq!(hashtags("#_ #__ #a_b"), vec!["#_", "#__", "#a_b"]);
+        assert_eq!(mentions("@___ @a_b"), vec!["@___", "@a_b"]);
+    }
+
+    #[test]
+    fn test_underscore_only_tokens_dropped_by_validation() {
+        let text = "#_ #__ #a_b #_1_ @___ @_._ @a_b";
+        let alphanumeric = Extractor::builder()
+            .require_alphanumeric(true)
+            .build()
+            .unwrap();
+        let result = alphanumeric.parse(text);
+        assert_eq!(result.hashtags, vec!["#a_b", "#_1_"]);
+        assert_eq!(result.mentions, vec!["@a_b"]);
+        let (_, rejected) = alphanumeric.validate(text);
+        assert_eq!(rejected[0].token, "#_");
+        assert!(rejected
+            .iter()
+            .all(|r| r.reason == RejectReason::NoLetterOrDigit));
+
+        let alpha = Extractor::builder().require_alpha(true).build().unwrap();
+        assert_eq!(alpha.parse(text).hashtags, vec!["#a_b"]);
+    }
+
+    // === Hashtag Diff ===
+    #[test]
+    fn test_hashtag_diff_added_and_removed() {
+        let (added, removed) = hashtag_diff(
+            "#keep #gone #keep #also_gone",
+            "#new #keep #Gone #new #later",
+        );
+        assert_eq!(added, vec!["#new", "#Gone", "#later"]);
+        assert_eq!(removed, vec!["#gone", "#also_gone"]);
+    }
+
+    #[test]
+    fn test_hashtag_diff_identical_and_empty() {
+        let empty: Vec<String> = Vec::new();
+        assert_eq!(
+            hashtag_diff("#a #b", "#b #a"),
+            (empty.clone(), empty.clone())
+        );
+        assert_eq!(
+            hashtag_diff("", "#a"),
+            (vec!["#a".to_string()], empty.clone())
+        );
+        assert_eq!(hashtag_diff("#a", ""), (empty, vec!["#a".to_string()]));
+    }
+
+    // === Reusable Sets ===
+    #[test]
+    fn test_parse_into_reuses_set() {
+        let mut mentions_seen = HashSet::new();
+        let mut tags = HashSet::new();
+        for text in ["@a #x me@mail.com", "@b @a #y", ""] {
+            parse_mentions_into(text, &mut mentions_seen);
+            parse_hashtags_into(text, &mut tags);
+        }
+        let expected: HashSet<String> = ["@a", "@b"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(mentions_seen, expected);
+        assert_eq!(tags.len(), 2);
+
+        tags.clear();
+        parse_hashtags_into("#z", &mut tags);
+        assert!(tags.contains("#z") && tags.len() == 1);
+    }
+
+    // === Emoji-Adjacent Tokens ===
+    #[test]
+    fn test_tokens_directly_after_emoji() {
+        let text = "🔥#fyp 👉@user ❤️#love 👍🏽@thumbs 1️⃣#one 👨‍👩‍👧#family 🇫🇷@paris";
+        assert_eq!(hashtags(text), vec!["#fyp", "#love", "#one", "#family"]);
+        assert_eq!(mentions(text), vec!["@user", "@thumbs", "@paris"]);
+    }
+
+    #[test]
+    fn test_emoji_counts_as_boundary() {
+        let extractor = Extractor::builder().require_boundary(true).build().unwrap();
+        let result = extractor.parse("🔥#fyp ❤️#love 👍🏽@thumbs foo#no a.#no ©#c");
+        assert_eq!(result.hashtags, vec!["#fyp", "#love", "#c"]);
+        assert_eq!(result.mentions, vec!["@thumbs"]);
+        assert!(extractor.parse("1#no *#no").hashtags.is_empty());
+    }
+
+    // === Mention Classification ===
+    #[test]
+    fn test_classify_mentions_case_insensitive() {
+        let brands: HashSet<String> = ["@DiorOfficial", "gucci", "＠Nike"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let text = "@diorofficial @GUCCI ＠nike @nike @MrBeast @gucciFan @MrBeast";
+        let (brand, other) = classify_mentions(text, &brands);
+        assert_eq!(brand, vec!["@diorofficial", "@GUCCI", "＠nike", "@nike"]);
+        assert_eq!(other, vec!["@MrBeast", "@gucciFan"]);
+    }
+
+    #[test]
+    fn test_classify_mentions_empty_brands() {
+        let (brand, other) = classify_mentions("@a @b", &HashSet::new());
+        assert!(brand.is_empty());
+        assert_eq!(other, vec!["@a", "@b"]);
+    }
+
+    // === Maximum Token Length ===
+    #[test]
+    fn test_validating_extractor_drops_tokens_over_max_token_len() {
+        let extractor = ExtractorBuilder::validating().build().unwrap();
+        let at_limit = format!("#{}", "é".repeat(MAX_TOKEN_LEN));
+        let over_limit = format!("#{}", "a".repeat(MAX_TOKEN_LEN + 1));
+        let text = format!("{at_limit} {over_limit} #ok");
+        assert_eq!(
+            extractor.parse(&text).hashtags,
+            vec![at_limit.clone(), "#ok".to_string()]
+        );
+        let (_, rejected) = extractor.validate(&text);
+        assert_eq!(rejected[0].reason, RejectReason::TooLong);
+        assert_eq!(hashtags(&text).len(), 3);
+    }
+
+    #[test]
+    fn test_default_extractor_has_no_length_limit() {
+        let long = format!("@{}", "x".repeat(10_000));
+        let default = Extractor::builder().build().unwrap();
+        assert_eq!(default.parse(&long).mentions, vec![long.clone()]);
+        let unlimited = ExtractorBuilder::validating()
+            .max_len(usize::MAX)
+            .build()
+            .unwrap();
+        assert_eq!(unlimited.parse(&long).mentions, vec![long.clone()]);
+        let strict = Extractor::builder().max_len(5).build().unwrap();
+        assert!(strict.parse("@abcdef").mentions.is_empty());
+    }
+
+    // === Token Spans ===
+    #[test]
+    fn test_parse_token_spans_matches_parse_tokens() {
+        let text = "#a @b me@mail.com #a";
+        let spans = parse_token_spans(text);
+        let tuples = parse_tokens(text);
+        assert_eq!(spans.len(), tuples.len());
+        for (span, (token, start, end)) in spans.iter().zip(tuples) {
+            assert_eq!(span.kind, token.kind());
+            assert_eq!(span.text, token.as_str());
+            assert_eq!((span.start, span.end), (start, end));
+        }
+    }
+
+    // === Collapse Substrings ===
+    #[test]
+    fn test_collapse_substrings_keeps_longer_prefix() {
+        let extractor = Extractor::builder()
+            .collapse_substrings(true)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#foryou #ForYouPage #fyp #fy @mr @MrBeast");
+        assert_eq!(result.hashtags, vec!["#ForYouPage", "#fyp"]);
+        assert_eq!(result.mentions, vec!["@MrBeast"]);
+    }
+
+    #[test]
+    fn test_collapse_substrings_is_prefix_only() {
+        let extractor = Extractor::builder()
+            .collapse_substrings(true)
+            .build()
+            .unwrap();
+        let result = extractor.parse("#you #foryou @foryou");
+        // Not a prefix, and mentions don't collapse hashtags.
+        assert_eq!(result.hashtags, vec!["#you", "#foryou"]);
+        assert_eq!(result.mentions, vec!["@foryou"]);
+    }
+
+    #[test]
+    fn test_collapse_substrings_off_by_default() {
+        let result = Extractor::builder()
+            .build()
+            .unwrap()
+            .parse("#foryou #foryoupage");
+        assert_eq!(result.hashtags, vec!["#foryou", "#foryoupage"]);
+    }
+
+    // === Longest / Shortest ===
+    #[test]
+    fn test_longest_and_shortest_hashtag() {
+        let text = "#abc #日本語 #xy #abcd #zz";
+        // `#日本語` is 3 chars but 10 bytes; `#abcd` wins on chars.
+        assert_eq!(longest_hashtag(text).as_deref(), Some("#abcd"));
+        assert_eq!(shortest_hashtag(text).as_deref(), Some("#xy"));
+        assert_eq!(longest_hashtag("#日本語 #abc").as_deref(), Some("#日本語"));
+        assert_eq!(shortest_hashtag("no tags"), None);
+    }
+
+    #[test]
+    fn test_longest_and_shortest_mention() {
+        let text = "@bob @alice me@averylongdomain.example @carol";
+        assert_eq!(longest_mention(text).as_deref(), Some("@alice"));
+        assert_eq!(shortest_mention(text).as_deref(), Some("@bob"));
+        assert_eq!(longest_mention("#only_tags"), None);
+    }
+
+    #[test]
+    fn test_longest_matches_primary_strategy() {
+        let text = "#fyp #dance #viral #trend";
+        assert_eq!(
+            longest_hashtag(text),
+            primary_hashtag(text, PrimaryStrategy::Longest)
+        );
     }
 }